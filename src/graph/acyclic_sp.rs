@@ -0,0 +1,143 @@
+use std::f32;
+use std::rc::Rc;
+use super::directed_edge::DirectedEdge;
+use super::edge_weighted_digraph::EdgeWeightedDigraph;
+
+// 无环加权有向图的最短路径，按拓扑顺序放松边，图中不能有环
+pub struct AcyclicSP {
+    edge_to: Vec<Option<Rc<DirectedEdge>>>, // 路径
+    dist_to: Vec<f32>,                      // 起点到顶点的距离
+}
+
+impl AcyclicSP {
+    pub fn new(g: &EdgeWeightedDigraph, s: usize) -> Self {
+        let mut this = AcyclicSP {
+            edge_to: Vec::with_capacity(g.v()),
+            dist_to: Vec::with_capacity(g.v()),
+        };
+
+        for _ in 0..g.v() {
+            this.edge_to.push(None);
+            this.dist_to.push(f32::INFINITY);
+        }
+
+        this.dist_to[s] = 0.0;
+
+        for v in Self::topological_order(g) {
+            this.relax(g, v);
+        }
+
+        this
+    }
+
+    // 拓扑排序：深度优先搜索的逆后序
+    fn topological_order(g: &EdgeWeightedDigraph) -> Vec<usize> {
+        let mut marked = vec![false; g.v()];
+        let mut post = Vec::with_capacity(g.v());
+
+        for v in 0..g.v() {
+            if ! marked[v] {
+                Self::dfs(g, v, &mut marked, &mut post);
+            }
+        }
+
+        post.reverse();
+        post
+    }
+
+    fn dfs(g: &EdgeWeightedDigraph, v: usize, marked: &mut Vec<bool>, post: &mut Vec<usize>) {
+        marked[v] = true;
+
+        for e in g.adj(v) {
+            if ! marked[e.to()] {
+                Self::dfs(g, e.to(), marked, post);
+            }
+        }
+
+        post.push(v);
+    }
+
+    // 放松顶点指出的所有边
+    fn relax(&mut self, g: &EdgeWeightedDigraph, v: usize) {
+        for e in g.adj(v) {
+            let w = e.to();
+
+            if self.dist_to[w] > self.dist_to[v] + e.weight() {
+                self.dist_to[w] = self.dist_to[v] + e.weight();
+                self.edge_to[w] = Some(e.clone());
+            }
+        }
+    }
+
+    // 起点到顶点的距离，不可达时为无穷大
+    pub fn dist_to(&self, v: usize) -> f32 {
+        self.dist_to[v]
+    }
+
+    pub fn has_path_to(&self, v: usize) -> bool {
+        self.dist_to[v] < f32::INFINITY
+    }
+
+    // 起点到顶点的最短路径上的边
+    pub fn path_to(&self, v: usize) -> Option<Vec<Rc<DirectedEdge>>> {
+        if ! self.has_path_to(v) {
+            return None
+        }
+
+        let mut path = Vec::new();
+        let mut x = v;
+
+        while let Some(ref e) = self.edge_to[x] {
+            path.push(e.clone());
+            x = e.from();
+        }
+
+        path.reverse();
+
+        Some(path)
+    }
+}
+
+
+#[test]
+fn test() {
+    let tiny_ewdag = [
+        (5, 4, 0.35), (4, 7, 0.37), (5, 7, 0.28), (5, 1, 0.32),
+        (4, 0, 0.38), (0, 2, 0.26), (3, 7, 0.39), (1, 3, 0.29),
+        (7, 2, 0.34), (6, 2, 0.40), (3, 6, 0.52), (6, 0, 0.58),
+        (6, 4, 0.93),
+    ];
+
+    let mut g = EdgeWeightedDigraph::with_capacity(8);
+
+    for &(v, w, weight) in tiny_ewdag.iter() {
+        g.add_edge(DirectedEdge::new(v, w, weight));
+    }
+
+    let sp = AcyclicSP::new(&g, 5);
+
+    //    5 to 0 (0.73): 5->4 0.35 4->0 0.38
+    //    5 to 1 (0.32): 5->1 0.32
+    //    5 to 2 (0.62): 5->7 0.28 7->2 0.34
+    //    5 to 3 (0.61): 5->1 0.32 1->3 0.29
+    //    5 to 4 (0.35): 5->4 0.35
+    //    5 to 5 (0.00):
+    //    5 to 6 (1.13): 5->1 0.32 1->3 0.29 3->6 0.52
+    //    5 to 7 (0.28): 5->7 0.28
+    assert!((sp.dist_to(2) - 0.62).abs() < 1e-6);
+    assert!((sp.dist_to(6) - 1.13).abs() < 1e-6);
+    assert_eq!(sp.dist_to(5), 0.0);
+
+    let path: Vec<(usize, usize)> = sp.path_to(6).unwrap()
+        .iter()
+        .map(|e| (e.from(), e.to()))
+        .collect();
+
+    assert_eq!(path, [(5, 1), (1, 3), (3, 6)]);
+    assert_eq!(sp.path_to(5).unwrap().len(), 0);
+
+    // 6 不能到达 5
+    let sp = AcyclicSP::new(&g, 6);
+    assert!(! sp.has_path_to(5));
+    assert_eq!(sp.path_to(5), None);
+}
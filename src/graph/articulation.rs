@@ -0,0 +1,95 @@
+use super::graph::Graph;
+
+// 割点：删除后会使图不连通的顶点，使用深度优先搜索的 low-link 算法
+pub struct ArticulationPoints {
+    pre: Vec<Option<usize>>,    // 顶点被访问的次序
+    low: Vec<usize>,            // 顶点通过子树和一条回边能到达的最小次序
+    count: usize,               // 已访问的顶点数量
+    is_point: Vec<bool>,        // 是否为割点
+}
+
+impl ArticulationPoints {
+    pub fn new(g: &Graph) -> Self {
+        let mut this = ArticulationPoints {
+            pre: vec![None; g.v()],
+            low: vec![0; g.v()],
+            count: 0,
+            is_point: vec![false; g.v()],
+        };
+
+        for v in 0..g.v() {
+            if this.pre[v].is_none() {
+                this.dfs(g, None, v);
+            }
+        }
+
+        this
+    }
+
+    fn dfs(&mut self, g: &Graph, parent: Option<usize>, v: usize) {
+        self.pre[v] = Some(self.count);
+        self.low[v] = self.count;
+        self.count += 1;
+
+        let mut children = 0;
+
+        for &w in g.adj(v) {
+            match self.pre[w] {
+                None => {
+                    children += 1;
+                    self.dfs(g, Some(v), w);
+                    self.low[v] = self.low[v].min(self.low[w]);
+
+                    // 非根顶点：w 的子树无法绕过 v 回到更早的顶点
+                    if parent.is_some() && Some(self.low[w]) >= self.pre[v] {
+                        self.is_point[v] = true;
+                    }
+                },
+                Some(pre_w) => {
+                    if Some(w) != parent {
+                        self.low[v] = self.low[v].min(pre_w);
+                    }
+                },
+            }
+        }
+
+        // 根顶点：有两个及以上的子树时才是割点
+        if parent.is_none() && children > 1 {
+            self.is_point[v] = true;
+        }
+    }
+
+    pub fn is_articulation(&self, v: usize) -> bool {
+        self.is_point[v]
+    }
+
+    // 所有割点，由小到大排列
+    pub fn points(&self) -> Vec<usize> {
+        (0..self.is_point.len()).filter(|&v| self.is_point[v]).collect()
+    }
+}
+
+
+#[test]
+fn test() {
+    // 两个三角形共用顶点 2
+    let edges = [(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 2)];
+    let g = Graph::from_edges(5, edges.iter().cloned());
+
+    let ap = ArticulationPoints::new(&g);
+    assert_eq!(ap.points(), [2]);
+    assert!(ap.is_articulation(2));
+    assert!(! ap.is_articulation(0));
+
+    // 根顶点 0 只有一个子树，不是割点；1 是割点
+    let g = Graph::from_edges(3, [(0, 1), (1, 2)].iter().cloned());
+    assert_eq!(ArticulationPoints::new(&g).points(), [1]);
+
+    // 根顶点 0 有两个子树
+    let g = Graph::from_edges(3, [(0, 1), (0, 2)].iter().cloned());
+    assert_eq!(ArticulationPoints::new(&g).points(), [0]);
+
+    // 环中没有割点
+    let g = Graph::from_edges(4, [(0, 1), (1, 2), (2, 3), (3, 0)].iter().cloned());
+    assert_eq!(ArticulationPoints::new(&g).points(), []);
+}
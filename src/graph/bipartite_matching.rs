@@ -0,0 +1,104 @@
+use super::graph::Graph;
+use super::two_color::TwoColor;
+
+// 二分图的最大匹配，使用增广路径
+pub struct BipartiteMatching {
+    mate: Vec<Option<usize>>,   // mate[v], 与顶点 v 匹配的顶点
+    size: usize,                // 匹配的边数
+}
+
+impl BipartiteMatching {
+    // 不是二分图时返回 None
+    pub fn new(g: &Graph) -> Option<Self> {
+        let two_color = TwoColor::new(g);
+
+        if ! two_color.is_two_colorable() {
+            return None
+        }
+
+        let mut this = BipartiteMatching {
+            mate: vec![None; g.v()],
+            size: 0,
+        };
+
+        // 从一侧的每个顶点出发寻找增广路径
+        for v in 0..g.v() {
+            if ! two_color.color(v) {
+                let mut marked = vec![false; g.v()];
+
+                if this.augment(g, v, &mut marked) {
+                    this.size += 1;
+                }
+            }
+        }
+
+        Some(this)
+    }
+
+    // 深度优先搜索增广路径，找到后沿路径交换匹配边
+    fn augment(&mut self, g: &Graph, v: usize, marked: &mut Vec<bool>) -> bool {
+        for &w in g.adj(v) {
+            if marked[w] {
+                continue
+            }
+
+            marked[w] = true;
+
+            let free = match self.mate[w] {
+                Some(u) => self.augment(g, u, marked),
+                None => true,
+            };
+
+            if free {
+                self.mate[v] = Some(w);
+                self.mate[w] = Some(v);
+                return true
+            }
+        }
+
+        false
+    }
+
+    // 匹配的边，每条边以 (v, w) 表示，其中 v < w
+    pub fn matching(&self) -> Vec<(usize, usize)> {
+        let mut res = Vec::with_capacity(self.size);
+
+        for (v, mate) in self.mate.iter().enumerate() {
+            if let Some(w) = *mate {
+                if v < w {
+                    res.push((v, w));
+                }
+            }
+        }
+
+        res
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+}
+
+
+#[test]
+fn test() {
+    // 左侧 0 1 2，右侧 3 4 5，先匹配 0-3 之后 1 需要增广路径
+    let edges = [(0, 3), (0, 4), (1, 3), (2, 3), (2, 5)];
+    let g = Graph::from_edges(6, edges.iter().cloned());
+
+    let matching = BipartiteMatching::new(&g).unwrap();
+    assert_eq!(matching.size(), 3);
+    assert_eq!(matching.matching(), [(0, 4), (1, 3), (2, 5)]);
+
+    // 1 和 2 只能与 3 匹配
+    let edges = [(0, 3), (0, 4), (1, 3), (2, 3)];
+    let g = Graph::from_edges(5, edges.iter().cloned());
+
+    let matching = BipartiteMatching::new(&g).unwrap();
+    assert_eq!(matching.size(), 2);
+    assert_eq!(matching.matching().len(), 2);
+
+    // 奇数环不是二分图
+    let g = Graph::from_edges(3, [(0, 1), (1, 2), (2, 0)].iter().cloned());
+    assert!(BipartiteMatching::new(&g).is_none());
+}
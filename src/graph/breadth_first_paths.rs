@@ -74,6 +74,58 @@ impl BreadthFirstPaths {
 
         Some(res)
     }
+
+    // 从起点可达的所有顶点，包括起点，由小到大排列
+    pub fn reachable(&self) -> Vec<usize> {
+        (0..self.marked.len()).filter(|&v| self.marked[v]).collect()
+    }
+}
+
+// 从起点出发不超过 max_hops 条边可达的所有顶点，包括起点，由小到大排列
+pub fn reachable_within(g: &Graph, s: usize, max_hops: usize) -> Vec<usize> {
+    let dist_to = distances(g, s, max_hops);
+    (0..g.v()).filter(|&v| dist_to[v].is_some()).collect()
+}
+
+// 与起点距离为 d 的顶点数量，下标为 d，不可达的顶点不计入
+pub fn distance_histogram(g: &Graph, s: usize) -> Vec<usize> {
+    let mut histogram = Vec::new();
+
+    for d in distances(g, s, usize::MAX).into_iter().flatten() {
+        if d >= histogram.len() {
+            histogram.resize(d + 1, 0);
+        }
+
+        histogram[d] += 1;
+    }
+
+    histogram
+}
+
+// 逐层广度优先搜索，到起点的边数，超过 max_hops 或不可达时为 None
+fn distances(g: &Graph, s: usize, max_hops: usize) -> Vec<Option<usize>> {
+    let mut dist_to = vec![None; g.v()];
+    let mut queue = VecDeque::new();
+    dist_to[s] = Some(0);
+    queue.push_back(s);
+
+    while let Some(v) = queue.pop_front() {
+        let d = dist_to[v].unwrap();
+
+        // 到达跳数上限的顶点不再向外扩展
+        if d == max_hops {
+            continue
+        }
+
+        for &w in g.adj(v) {
+            if dist_to[w].is_none() {
+                dist_to[w] = Some(d + 1);
+                queue.push_back(w);
+            }
+        }
+    }
+
+    dist_to
 }
 
 #[test]
@@ -95,4 +147,46 @@ fn test() {
     assert!(! bfp.has_path_to(9));
     assert_eq!(bfp.path_to(4), Some(vec![0, 5, 4]));
     assert_eq!(bfp.path_to(9), None);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_reachable() {
+    let tiny_cg = [
+        (0, 5), (2, 4), (2, 3), (1, 2), (0, 1), (3, 4), (3, 5), (0, 2),
+    ];
+
+    // 顶点 6 和 7 与其他顶点不连通
+    let mut g = Graph::from_edges(8, tiny_cg.iter().cloned());
+    g.add_edge(6, 7);
+
+    let bfp = BreadthFirstPaths::new(&g, 0);
+
+    assert_eq!(bfp.reachable().len(), 6);
+    assert_eq!(bfp.reachable(), [0, 1, 2, 3, 4, 5]);
+    assert_eq!(BreadthFirstPaths::new(&g, 7).reachable(), [6, 7]);
+}
+
+#[test]
+fn test_reachable_within() {
+    // 路径 0-1-2-...-9
+    let g = Graph::from_edges(10, (0..9).map(|v| (v, v + 1)));
+
+    assert_eq!(reachable_within(&g, 5, 2), [3, 4, 5, 6, 7]);
+    assert_eq!(reachable_within(&g, 5, 0), [5]);
+    assert_eq!(reachable_within(&g, 0, 2), [0, 1, 2]);
+    assert_eq!(reachable_within(&g, 5, 100).len(), 10);
+}
+
+#[test]
+fn test_distance_histogram() {
+    let g = Graph::from_edges(6, (0..5).map(|v| (v, v + 1)));
+    assert_eq!(distance_histogram(&g, 0), [1, 1, 1, 1, 1, 1]);
+    assert_eq!(distance_histogram(&g, 2), [1, 2, 2, 1]);
+
+    // 不可达的顶点不计入
+    let mut g = Graph::from_edges(8, vec![(0, 1), (0, 2), (0, 3), (1, 4), (2, 4)]);
+    g.add_edge(6, 7);
+
+    assert_eq!(distance_histogram(&g, 0), [1, 3, 1]);
+    assert_eq!(distance_histogram(&g, 5), [1]);
+}
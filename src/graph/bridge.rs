@@ -0,0 +1,91 @@
+use super::graph::Graph;
+use super::cc::CC;
+
+// 桥：删除后会使图不连通的边，使用深度优先搜索的 low-link 算法
+pub struct Bridge {
+    pre: Vec<Option<usize>>,        // 顶点被访问的次序
+    low: Vec<usize>,                // 顶点通过子树和一条回边能到达的最小次序
+    count: usize,                   // 已访问的顶点数量
+    bridges: Vec<(usize, usize)>,   // 桥，以 (父顶点, 子顶点) 表示
+}
+
+impl Bridge {
+    pub fn new(g: &Graph) -> Self {
+        let mut this = Bridge {
+            pre: vec![None; g.v()],
+            low: vec![0; g.v()],
+            count: 0,
+            bridges: Vec::new(),
+        };
+
+        for v in 0..g.v() {
+            if this.pre[v].is_none() {
+                this.dfs(g, None, v);
+            }
+        }
+
+        this
+    }
+
+    fn dfs(&mut self, g: &Graph, parent: Option<usize>, v: usize) {
+        self.pre[v] = Some(self.count);
+        self.low[v] = self.count;
+        self.count += 1;
+
+        // 只跳过一次指向父顶点的边，平行边仍然算作回边
+        let mut skipped_parent = false;
+
+        for &w in g.adj(v) {
+            match self.pre[w] {
+                None => {
+                    self.dfs(g, Some(v), w);
+                    self.low[v] = self.low[v].min(self.low[w]);
+
+                    // w 的子树无法绕过 v-w 回到更早的顶点
+                    if Some(self.low[w]) == self.pre[w] {
+                        self.bridges.push((v, w));
+                    }
+                },
+                Some(pre_w) => {
+                    if Some(w) == parent && ! skipped_parent {
+                        skipped_parent = true;
+                    }
+                    else {
+                        self.low[v] = self.low[v].min(pre_w);
+                    }
+                },
+            }
+        }
+    }
+
+    pub fn bridges(&self) -> Vec<(usize, usize)> {
+        self.bridges.clone()
+    }
+}
+
+// 是否为连通图
+pub fn is_connected(g: &Graph) -> bool {
+    CC::new(g).count() <= 1
+}
+
+
+#[test]
+fn test() {
+    // 两个三角形由 2-3 相连
+    let edges = [(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 5), (5, 3)];
+    let mut g = Graph::from_edges(6, edges.iter().cloned());
+
+    assert_eq!(Bridge::new(&g).bridges(), [(2, 3)]);
+    assert!(is_connected(&g));
+
+    // 平行边不是桥
+    g.add_edge(2, 3);
+    assert_eq!(Bridge::new(&g).bridges(), []);
+
+    // 树的每条边都是桥
+    let g = Graph::from_edges(4, [(0, 1), (1, 2), (1, 3)].iter().cloned());
+    assert_eq!(Bridge::new(&g).bridges().len(), 3);
+
+    let g = Graph::from_edges(4, [(0, 1), (2, 3)].iter().cloned());
+    assert!(! is_connected(&g));
+}
@@ -0,0 +1,89 @@
+use super::graph::Graph;
+use std::collections::VecDeque;
+
+// 连通分量，使用广度优先搜索，不会因递归过深而栈溢出
+pub struct CCBfs {
+    marked: Vec<bool>,
+    id: Vec<usize>,
+    count: usize,
+}
+
+impl CCBfs {
+    pub fn new(g: &Graph) -> Self {
+        let mut this = CCBfs {
+            marked: vec![false; g.v()],
+            id: vec![0; g.v()],
+            count: 0,
+        };
+
+        for v in 0..g.v() {
+            if ! this.marked[v] {
+                this.bfs(g, v);
+                this.count += 1;
+            }
+        }
+
+        this
+    }
+
+    fn bfs(&mut self, g: &Graph, s: usize) {
+        let mut queue = VecDeque::new();
+        queue.push_back(s);
+        self.marked[s] = true;
+        self.id[s] = self.count;
+
+        while let Some(v) = queue.pop_front() {
+            for w in g.adj(v) {
+                if ! self.marked[*w] {
+                    self.marked[*w] = true;
+                    self.id[*w] = self.count;
+                    queue.push_back(*w);
+                }
+            }
+        }
+    }
+
+    // 给定两个顶点，判断是否为同一连通分量
+    pub fn connected(&self, v: usize, w: usize) -> bool {
+        self.id[v] == self.id[w]
+    }
+
+    pub fn id(&self) -> &Vec<usize> {
+        &self.id
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+#[test]
+fn test() {
+    use super::cc::CC;
+
+    let tiny_cg = [
+        (0, 5), (2, 4), (2, 3), (1, 2), (0, 1), (3, 4), (3, 5), (0, 2),
+    ];
+
+    let mut g = Graph::from_edges(8, tiny_cg.iter().cloned());
+    g.add_edge(6, 7);
+
+    let cc = CC::new(&g);
+    let cc_bfs = CCBfs::new(&g);
+
+    assert_eq!(cc_bfs.count(), 2);
+    assert_eq!(cc_bfs.count(), cc.count());
+    assert_eq!(cc_bfs.id(), cc.id());
+    assert!(cc_bfs.connected(0, 4));
+    assert!(! cc_bfs.connected(5, 6));
+}
+
+#[test]
+fn test_long_path() {
+    let n = 100000;
+    let g = Graph::from_edges(n, (1..n).map(|v| (v - 1, v)));
+    let cc = CCBfs::new(&g);
+
+    assert_eq!(cc.count(), 1);
+    assert!(cc.connected(0, n - 1));
+}
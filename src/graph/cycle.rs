@@ -1,16 +1,26 @@
 use super::graph::Graph;
+use std::collections::VecDeque;
 
 // 环检测
 pub struct Cycle {
     marked: Vec<bool>,
     has_cycle: bool,
+    allow_self_loops: bool,     // 自环不算作环
+    allow_parallel: bool,       // 平行边不算作环
 }
 
 impl Cycle {
+    // 与 Sedgewick 的实现相同，自环和平行边都算作环
     pub fn new(g: &Graph) -> Self {
+        Cycle::with_options(g, false, false)
+    }
+
+    pub fn with_options(g: &Graph, allow_self_loops: bool, allow_parallel: bool) -> Self {
         let mut this = Cycle {
             marked: Vec::with_capacity(g.v()),
             has_cycle: false,
+            allow_self_loops,
+            allow_parallel,
         };
 
         for _ in 0..g.v() {
@@ -19,21 +29,46 @@ impl Cycle {
 
         for s in 0..g.v() {
             if ! this.marked[s] {
-                this.dfs(g, s, s);
+                this.dfs(g, s, None);
             }
         }
 
         this
     }
 
-    fn dfs(&mut self, g: &Graph, v: usize, u: usize) {
+    fn dfs(&mut self, g: &Graph, v: usize, parent: Option<usize>) {
         self.marked[v] = true;
 
-        for w in g.adj(v) {
-            if ! self.marked[*w] {
-                self.dfs(g, *w, v);
+        // 允许平行边时，重复的相邻顶点只访问一次，只有这时才复制邻接表
+        let deduped;
+        let adj: &[usize] = if self.allow_parallel {
+            let mut copy = g.adj(v).clone();
+            copy.sort();
+            copy.dedup();
+            deduped = copy;
+            &deduped
+        }
+        else {
+            g.adj(v)
+        };
+
+        let mut skipped_parent = false;
+
+        for &w in adj {
+            if w == v {
+                if ! self.allow_self_loops {
+                    self.has_cycle = true;
+                }
             }
-            else if *w == u {
+            else if ! self.marked[w] {
+                self.dfs(g, w, Some(v));
+            }
+            // 回到父节点的树边只跳过一次，再次出现说明有平行边
+            else if Some(w) == parent && ! skipped_parent {
+                skipped_parent = true;
+            }
+            // 已访问且不是父节点，说明有环
+            else {
                 self.has_cycle = true;
             }
         }
@@ -44,6 +79,53 @@ impl Cycle {
     }
 }
 
+// 围长：最短环的长度，自环长度为 1，平行边长度为 2，森林返回 None
+// 从每个顶点广度优先搜索，遇到已访问且不是父节点的顶点时，经过起点的环长度不超过两端距离之和加一，O(VE)
+pub fn girth(g: &Graph) -> Option<usize> {
+    let mut girth: Option<usize> = None;
+
+    for s in 0..g.v() {
+        let mut dist_to = vec![None; g.v()];
+        let mut edge_to = vec![None; g.v()];
+        let mut queue = VecDeque::new();
+        dist_to[s] = Some(0);
+        queue.push_back(s);
+
+        while let Some(v) = queue.pop_front() {
+            let d = dist_to[v].unwrap();
+
+            // 之后找到的环长度至少为 2d，不会比已知的最短环更短
+            if girth.map_or(false, |girth| 2 * d >= girth) {
+                break
+            }
+
+            let mut skipped_parent = false;
+
+            for &w in g.adj(v) {
+                let len = match dist_to[w] {
+                    None => {
+                        dist_to[w] = Some(d + 1);
+                        edge_to[w] = Some(v);
+                        queue.push_back(w);
+                        continue
+                    },
+                    Some(_) if w == v => 1,
+                    // 回到父节点的树边只跳过一次，再次出现说明有平行边
+                    Some(_) if edge_to[v] == Some(w) && ! skipped_parent => {
+                        skipped_parent = true;
+                        continue
+                    },
+                    Some(dw) => d + dw + 1,
+                };
+
+                girth = Some(girth.map_or(len, |girth| girth.min(len)));
+            }
+        }
+    }
+
+    girth
+}
+
 #[test]
 fn test() {
     let tiny_g = [
@@ -59,4 +141,69 @@ fn test() {
 
     let cycle = Cycle::new(&g);
     assert!(cycle.has_cycle());
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_acyclic() {
+    let g = Graph::from_edges(5, vec![(0, 1), (1, 2), (1, 3), (3, 4)]);
+    assert!(! Cycle::new(&g).has_cycle());
+
+    let g = Graph::from_edges(3, vec![(0, 1), (1, 2), (2, 0)]);
+    assert!(Cycle::new(&g).has_cycle());
+
+    let g = Graph::from_edges(2, vec![(0, 0)]);
+    assert!(Cycle::new(&g).has_cycle());
+}
+
+#[test]
+fn test_self_loop() {
+    // 唯一的环是自环
+    let g = Graph::from_edges(3, vec![(0, 1), (1, 2), (2, 2)]);
+
+    assert!(Cycle::new(&g).has_cycle());
+    assert!(Cycle::with_options(&g, false, true).has_cycle());
+    assert!(! Cycle::with_options(&g, true, false).has_cycle());
+    assert!(! Cycle::with_options(&g, true, true).has_cycle());
+}
+
+#[test]
+fn test_parallel_edges() {
+    // 唯一的环是 1-2 之间的平行边
+    let g = Graph::from_edges(4, vec![(0, 1), (1, 2), (2, 1), (2, 3)]);
+
+    assert!(Cycle::new(&g).has_cycle());
+    assert!(Cycle::with_options(&g, true, false).has_cycle());
+    assert!(! Cycle::with_options(&g, false, true).has_cycle());
+    assert!(! Cycle::with_options(&g, true, true).has_cycle());
+
+    // 允许平行边时，真正的环仍然能检测到
+    let g = Graph::from_edges(3, vec![(0, 1), (1, 0), (1, 2), (2, 0)]);
+    assert!(Cycle::with_options(&g, true, true).has_cycle());
+}
+
+#[test]
+fn test_girth() {
+    // 三角形 0-1-2 和五边形 2-3-4-5-6
+    let g = Graph::from_edges(7, vec![
+        (0, 1), (1, 2), (2, 0),
+        (2, 3), (3, 4), (4, 5), (5, 6), (6, 2),
+    ]);
+    assert_eq!(girth(&g), Some(3));
+
+    // 只有五边形
+    let g = Graph::from_edges(6, vec![(0, 1), (1, 2), (2, 3), (3, 4), (4, 0), (4, 5)]);
+    assert_eq!(girth(&g), Some(5));
+
+    // 偶数长度的环
+    let g = Graph::from_edges(4, vec![(0, 1), (1, 2), (2, 3), (3, 0)]);
+    assert_eq!(girth(&g), Some(4));
+
+    // 树
+    let g = Graph::from_edges(5, vec![(0, 1), (1, 2), (1, 3), (3, 4)]);
+    assert_eq!(girth(&g), None);
+    assert_eq!(girth(&Graph::with_capacity(3)), None);
+
+    // 平行边和自环
+    assert_eq!(girth(&Graph::from_edges(3, vec![(0, 1), (1, 2), (2, 1)])), Some(2));
+    assert_eq!(girth(&Graph::from_edges(3, vec![(0, 1), (1, 2), (2, 2)])), Some(1));
+}
@@ -66,6 +66,67 @@ impl DepthFirstPaths {
 
         Some(res)
     }
+
+    // 路径的边数，沿 edge_to 计数，不生成路径，不可达时返回 None
+    pub fn path_len(&self, v: usize) -> Option<usize> {
+        if ! self.has_path_to(v) {
+            return None
+        }
+
+        let mut len = 0;
+        let mut x = v;
+
+        while let Some(w) = self.edge_to[x] {
+            len += 1;
+            x = w;
+        }
+
+        Some(len)
+    }
+
+    // 路径上的边，以 (from, to) 表示，不可达时为空
+    pub fn edge_path_to(&self, v: usize) -> Vec<(usize, usize)> {
+        match self.path_to(v) {
+            Some(path) => path.windows(2).map(|pair| (pair[0], pair[1])).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    // 深度优先搜索树的边，以 (parent, child) 表示，按 child 排列
+    pub fn tree_edges(&self) -> Vec<(usize, usize)> {
+        self.edge_to.iter().enumerate().filter_map(|(w, v)| v.map(|v| (v, w))).collect()
+    }
+}
+
+// 从 s 到 t 的所有简单路径，同一条路径中不会重复经过顶点
+pub fn all_simple_paths(g: &Graph, s: usize, t: usize) -> Vec<Vec<usize>> {
+    let mut on_path = vec![false; g.v()];
+    let mut path = Vec::new();
+    let mut res = Vec::new();
+
+    simple_paths(g, s, t, &mut on_path, &mut path, &mut res);
+
+    res
+}
+
+// 回溯：进入顶点时加入路径，离开时移出路径
+fn simple_paths(g: &Graph, v: usize, t: usize, on_path: &mut Vec<bool>, path: &mut Vec<usize>, res: &mut Vec<Vec<usize>>) {
+    on_path[v] = true;
+    path.push(v);
+
+    if v == t {
+        res.push(path.clone());
+    }
+    else {
+        for w in g.adj(v) {
+            if ! on_path[*w] {
+                simple_paths(g, *w, t, on_path, path, res);
+            }
+        }
+    }
+
+    path.pop();
+    on_path[v] = false;
 }
 
 #[test]
@@ -87,4 +148,79 @@ fn test() {
     assert!(! dfp.has_path_to(9));
     assert_eq!(dfp.path_to(6), Some(vec![0, 5, 4, 6]));
     assert_eq!(dfp.path_to(9), None);
+}
+
+#[test]
+fn test_edge_path_to() {
+    let tiny_cg = [
+        (0, 5), (2, 4), (2, 3), (1, 2), (0, 1), (3, 4), (3, 5), (0, 2),
+    ];
+
+    let mut g = Graph::with_capacity(7);
+
+    for &(v, w) in tiny_cg.iter() {
+        g.add_edge(v, w);
+    }
+
+    let dfp = DepthFirstPaths::new(&g, 0);
+
+    assert_eq!(dfp.path_to(4), Some(vec![0, 5, 3, 2, 4]));
+    assert_eq!(dfp.edge_path_to(4), [(0, 5), (5, 3), (3, 2), (2, 4)]);
+    assert_eq!(dfp.edge_path_to(0), []);
+
+    // 6 是孤立的顶点
+    assert_eq!(dfp.edge_path_to(6), []);
+}
+
+#[test]
+fn test_all_simple_paths() {
+    // 0 - 1 - 3
+    //  \- 2 -/
+    let mut g = Graph::with_capacity(5);
+
+    for &(v, w) in [(0, 1), (1, 3), (0, 2), (2, 3), (3, 4)].iter() {
+        g.add_edge(v, w);
+    }
+
+    let mut paths = all_simple_paths(&g, 0, 3);
+    paths.sort();
+
+    assert_eq!(paths, [vec![0, 1, 3], vec![0, 2, 3]]);
+    assert_eq!(all_simple_paths(&g, 0, 0), [vec![0]]);
+    assert_eq!(all_simple_paths(&g, 4, 0).len(), 2);
+}
+
+#[test]
+fn test_tree_edges() {
+    let tiny_cg = [
+        (0, 5), (2, 4), (2, 3), (1, 2), (0, 1), (3, 4), (3, 5), (0, 2),
+    ];
+
+    // 顶点 6 不可达
+    let g = Graph::from_edges(7, tiny_cg.iter().cloned());
+    let dfp = DepthFirstPaths::new(&g, 0);
+    let edges = dfp.tree_edges();
+    let reachable = (0..g.v()).filter(|&v| dfp.has_path_to(v)).count();
+
+    assert_eq!(reachable, 6);
+    assert_eq!(edges.len(), reachable - 1);
+    assert_eq!(edges, [(2, 1), (3, 2), (5, 3), (2, 4), (0, 5)]);
+}
+
+#[test]
+fn test_path_len() {
+    let tiny_cg = [
+        (0, 5), (2, 4), (2, 3), (1, 2), (0, 1), (3, 4), (3, 5), (0, 2),
+    ];
+
+    let g = Graph::from_edges(7, tiny_cg.iter().cloned());
+    let dfp = DepthFirstPaths::new(&g, 0);
+
+    assert_eq!(dfp.path_len(4), Some(4));
+    assert_eq!(dfp.path_len(0), Some(0));
+    assert_eq!(dfp.path_len(6), None);
+
+    for v in 0..6 {
+        assert_eq!(dfp.path_len(v), Some(dfp.path_to(v).unwrap().len() - 1));
+    }
 }
\ No newline at end of file
@@ -4,6 +4,7 @@ use super::graph::Graph;
 pub struct DepthFirstSearch {
     marked: Vec<bool>,
     count: usize,
+    order: Vec<usize>,  // 顶点被标记的顺序
 }
 
 impl DepthFirstSearch {
@@ -11,6 +12,7 @@ impl DepthFirstSearch {
         let mut this = DepthFirstSearch {
             marked: Vec::with_capacity(g.v()),
             count: 0,
+            order: Vec::new(),
         };
 
         for _ in 0..g.v() {
@@ -25,6 +27,7 @@ impl DepthFirstSearch {
     fn dfs(&mut self, g: &Graph, v: usize) {
         self.marked[v] = true;
         self.count += 1;
+        self.order.push(v);
 
         for w in g.adj(v) {
             if ! self.marked[*w] {
@@ -40,6 +43,11 @@ impl DepthFirstSearch {
     pub fn count(&self) -> usize {
         self.count
     }
+
+    // 深度优先搜索访问顶点的顺序
+    pub fn order(&self) -> &[usize] {
+        &self.order
+    }
 }
 
 #[test]
@@ -60,4 +68,31 @@ fn test() {
     assert!(dfs.marked(4));
     assert!(! dfs.marked(12));
     assert_eq!(dfs.count(), 7);
+}
+
+#[test]
+fn test_order() {
+    let tiny_cg = [
+        (0, 5), (2, 4), (2, 3), (1, 2), (0, 1), (3, 4), (3, 5), (0, 2),
+    ];
+
+    let mut g = Graph::with_capacity(6);
+
+    for &(v, w) in tiny_cg.iter() {
+        g.add_edge(v, w);
+    }
+
+    let dfs = DepthFirstSearch::new(&g, 0);
+    assert_eq!(dfs.count(), 6);
+    assert!(dfs.marked(5));
+
+    let order = dfs.order();
+
+    assert_eq!(order[0], 0);
+    assert_eq!(order.len(), dfs.count());
+    assert_eq!(order, &[0, 5, 3, 2, 4, 1]);
+
+    let mut sorted = order.to_vec();
+    sorted.sort();
+    assert_eq!(sorted, [0, 1, 2, 3, 4, 5]);
 }
\ No newline at end of file
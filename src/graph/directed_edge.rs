@@ -0,0 +1,40 @@
+use std::f32;
+
+// 加权有向图中的边
+#[derive(Debug, Eq, PartialEq)]
+pub struct DirectedEdge {
+    v: usize,
+    w: usize,
+    weight: u32,
+}
+
+impl DirectedEdge {
+    pub fn new(v: usize, w: usize, weight: f32) -> Self {
+        DirectedEdge {v, w, weight: weight.to_bits()}
+    }
+
+    // 权重
+    pub fn weight(&self) -> f32 {
+        f32::from_bits(self.weight)
+    }
+
+    // 边的起点
+    pub fn from(&self) -> usize {
+        self.v
+    }
+
+    // 边的终点
+    pub fn to(&self) -> usize {
+        self.w
+    }
+}
+
+
+#[test]
+fn test() {
+    let edge = DirectedEdge::new(0, 5, 0.8);
+
+    assert_eq!(edge.weight(), 0.8);
+    assert_eq!(edge.from(), 0);
+    assert_eq!(edge.to(), 5);
+}
@@ -1,3 +1,4 @@
+use std::fmt;
 use std::cmp::Ordering;
 use std::f32;
 
@@ -19,12 +20,12 @@ impl Edge {
         f32::from_bits(self.weight)
     }
 
-    // 其中的1个顶点
+    // 其中的1个顶点，other(either()) 总是返回另一个顶点
     pub fn either(&self) -> usize {
         self.v
     }
 
-    // 给定一个顶点，返回另一个
+    // 给定一个顶点，返回另一个，v 不是这条边的顶点时返回 None
     pub fn other(&self, v: usize) -> Option<usize> {
         if v == self.v {
             Some(self.w)
@@ -51,6 +52,13 @@ impl PartialOrd for Edge {
     }
 }
 
+// 格式为 v-w weight，权重保留两位小数，例如 0-7 0.16
+impl fmt::Display for Edge {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}-{} {:.2}", self.v, self.w, self.weight())
+    }
+}
+
 
 #[test]
 fn test() {
@@ -69,4 +77,23 @@ fn test() {
     assert_eq!(edge.partial_cmp(&edge2), Some(Ordering::Greater));
     assert_eq!(edge2.partial_cmp(&edge), Some(Ordering::Less));
 
+}
+
+#[test]
+fn test_display() {
+    assert_eq!(format!("{}", Edge::new(0, 7, 0.16)), "0-7 0.16");
+    assert_eq!(Edge::new(6, 2, 0.4).to_string(), "6-2 0.40");
+}
+
+#[test]
+fn test_either_other() {
+    let edge = Edge::new(3, 8, 0.5);
+
+    assert_eq!(edge.other(edge.either()), Some(8));
+    assert_eq!(edge.other(8), Some(3));
+    assert_eq!(edge.other(4), None);
+
+    // 自环的另一个顶点是它自己
+    let edge = Edge::new(2, 2, 0.1);
+    assert_eq!(edge.other(edge.either()), Some(2));
 }
\ No newline at end of file
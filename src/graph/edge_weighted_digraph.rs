@@ -0,0 +1,121 @@
+use std::rc::Rc;
+use super::directed_edge::DirectedEdge;
+
+
+// 加权有向图
+pub struct EdgeWeightedDigraph {
+    v: usize,
+    e: usize,
+    adj: Vec<Vec<Rc<DirectedEdge>>>,
+    indegree: Vec<usize>,
+}
+
+impl EdgeWeightedDigraph {
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut this = EdgeWeightedDigraph {
+            v: capacity,
+            e: 0,
+            adj: Vec::with_capacity(capacity),
+            indegree: vec![0; capacity],
+        };
+
+        for _ in 0..capacity {
+            this.adj.push(Vec::new());
+        }
+
+        this
+    }
+
+    pub fn v(&self) -> usize {
+        self.v
+    }
+
+    pub fn e(&self) -> usize {
+        self.e
+    }
+
+    pub fn add_edge(&mut self, edge: DirectedEdge) {
+        let v = edge.from();
+
+        self.indegree[edge.to()] += 1;
+        self.adj[v].push(Rc::new(edge));
+        self.e += 1;
+    }
+
+    // 由顶点 v 指出的边的数量
+    pub fn out_degree(&self, v: usize) -> usize {
+        self.adj[v].len()
+    }
+
+    // 指向顶点 v 的边的数量
+    pub fn in_degree(&self, v: usize) -> usize {
+        self.indegree[v]
+    }
+
+    // 是否有从 v 指向 w 的边
+    pub fn has_edge(&self, v: usize, w: usize) -> bool {
+        self.adj[v].iter().any(|edge| edge.to() == w)
+    }
+
+    // 由顶点 v 指出的边
+    pub fn adj(&self, v: usize) -> &Vec<Rc<DirectedEdge>> {
+        &self.adj[v]
+    }
+
+    pub fn edges(&self) -> Vec<Rc<DirectedEdge>> {
+        let mut edges = Vec::with_capacity(self.e());
+
+        for v in 0..self.v() {
+            for edge in self.adj(v) {
+                edges.push(edge.clone());
+            }
+        }
+
+        edges
+    }
+}
+
+
+#[test]
+fn test() {
+    let tiny_ewdag = [
+        (5, 4, 0.35), (4, 7, 0.37), (5, 7, 0.28), (5, 1, 0.32),
+        (4, 0, 0.38), (0, 2, 0.26), (3, 7, 0.39), (1, 3, 0.29),
+        (7, 2, 0.34), (6, 2, 0.40), (3, 6, 0.52), (6, 0, 0.58),
+        (6, 4, 0.93),
+    ];
+
+    let mut g = EdgeWeightedDigraph::with_capacity(8);
+
+    for &(v, w, weight) in tiny_ewdag.iter() {
+        g.add_edge(DirectedEdge::new(v, w, weight));
+    }
+
+    assert_eq!(g.v(), 8);
+    assert_eq!(g.e(), 13);
+    assert_eq!(g.adj(5).len(), 3);
+    assert_eq!(g.adj(2).len(), 0);
+    assert_eq!(g.edges().len(), 13);
+}
+
+
+#[test]
+fn test_degree() {
+    let mut g = EdgeWeightedDigraph::with_capacity(4);
+
+    for &(v, w, weight) in [(0, 1, 0.5), (0, 2, 0.2), (1, 2, 0.3), (3, 2, 0.1), (2, 0, 0.4)].iter() {
+        g.add_edge(DirectedEdge::new(v, w, weight));
+    }
+
+    assert_eq!(g.out_degree(0), 2);
+    assert_eq!(g.in_degree(0), 1);
+    assert_eq!(g.out_degree(2), 1);
+    assert_eq!(g.in_degree(2), 3);
+    assert_eq!(g.out_degree(3), 1);
+    assert_eq!(g.in_degree(3), 0);
+
+    assert!(g.has_edge(0, 1));
+    assert!(! g.has_edge(1, 0));
+    assert!(g.has_edge(2, 0));
+    assert!(! g.has_edge(3, 0));
+}
\ No newline at end of file
@@ -1,5 +1,7 @@
 use std::rc::Rc;
+use std::str::FromStr;
 use super::edge::Edge;
+use super::graph::{parse_edges, ParseError};
 
 
 // 加权无向图
@@ -24,6 +26,17 @@ impl EdgeWeightedGraph {
         this
     }
 
+    // 给定顶点数量和 (v, w, weight) 形式的边，初始化图
+    pub fn from_edges<I: IntoIterator<Item = (usize, usize, f32)>>(capacity: usize, edges: I) -> Self {
+        let mut this = EdgeWeightedGraph::with_capacity(capacity);
+
+        for (v, w, weight) in edges {
+            this.add_edge(Edge::new(v, w, weight));
+        }
+
+        this
+    }
+
     pub fn v(&self) -> usize {
         self.v
     }
@@ -32,6 +45,13 @@ impl EdgeWeightedGraph {
         self.e
     }
 
+    // 为每个顶点的邻接表预留空间，批量添加边时减少重新分配，不影响结果
+    pub fn reserve_edges(&mut self, per_vertex: usize) {
+        for adj in self.adj.iter_mut() {
+            adj.reserve(per_vertex);
+        }
+    }
+
     pub fn add_edge(&mut self, edge: Edge) {
         let edge = Rc::new(edge);
         let v = edge.either();
@@ -64,19 +84,41 @@ impl EdgeWeightedGraph {
     }
 }
 
+// 文本格式：第一行为顶点数量，第二行为边数量，之后每行一条边 "v w weight"，忽略空行
+impl FromStr for EdgeWeightedGraph {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let (v, edges) = parse_edges(input, true)?;
+        let mut g = EdgeWeightedGraph::with_capacity(v);
+
+        for (a, b, weight) in edges {
+            g.add_edge(Edge::new(a, b, weight));
+        }
+
+        Ok(g)
+    }
+}
+
+// 《算法》中的 tinyEWG.txt，8 个顶点，16 条边
+#[cfg(test)]
+const TINY_EWG: [(usize, usize, f32); 16] = [
+    (4, 5, 0.35), (4, 7, 0.37), (5, 7, 0.28), (0, 7, 0.16),
+    (1, 5, 0.32), (0, 4, 0.38), (2, 3, 0.17), (1, 7, 0.19),
+    (0, 2, 0.26), (1, 2, 0.36), (1, 3, 0.39), (2, 7, 0.34),
+    (6, 2, 0.40), (3, 6, 0.52), (6, 0, 0.58), (6, 4, 0.93),
+];
+
+#[cfg(test)]
+pub fn tiny_ewg() -> EdgeWeightedGraph {
+    EdgeWeightedGraph::from_edges(8, TINY_EWG.iter().cloned())
+}
 
 #[test]
 fn test() {
-    let tiny_ewg = [
-        (4, 5, 0.35), (4, 7, 0.37), (5, 7, 0.28), (0, 7, 0.16),
-        (1, 5, 0.32), (0, 4, 0.38), (2, 3, 0.17), (1, 7, 0.19),
-        (0, 2, 0.26), (1, 2, 0.36), (1, 3, 0.39), (2, 7, 0.34),
-        (6, 2, 0.40), (3, 6, 0.52), (6, 0, 0.58), (6, 4, 0.93),
-    ];
-
     let mut g = EdgeWeightedGraph::with_capacity(8);
 
-    for &(v, w, weight) in tiny_ewg.iter() {
+    for &(v, w, weight) in TINY_EWG.iter() {
         g.add_edge(Edge::new(v, w, weight));
     }
 
@@ -84,4 +126,49 @@ fn test() {
     assert_eq!(g.e(), 16);
     assert_eq!(g.adj(0).len(), 4);
     assert_eq!(g.edges().len(), 16);
+}
+
+#[test]
+fn test_from_edges() {
+    let g = EdgeWeightedGraph::from_edges(8, TINY_EWG.iter().cloned());
+
+    assert_eq!(g.v(), 8);
+    assert_eq!(g.e(), 16);
+    assert_eq!(g.adj(0).len(), 4);
+}
+
+#[test]
+fn test_reserve_edges() {
+    let mut g = EdgeWeightedGraph::with_capacity(8);
+    g.reserve_edges(4);
+
+    assert!((0..g.v()).all(|v| g.adj(v).capacity() >= 4));
+    assert_eq!(g.e(), 0);
+
+    for &(v, w, weight) in TINY_EWG.iter() {
+        g.add_edge(Edge::new(v, w, weight));
+    }
+
+    assert_eq!(g.e(), 16);
+    assert_eq!(g.adj(0).len(), 4);
+    assert_eq!(g.edges().len(), 16);
+}
+
+#[test]
+fn test_from_str() {
+    use super::prim_mst::PrimMST;
+
+    let tiny_ewg: String = TINY_EWG.iter()
+        .map(|&(v, w, weight)| format!("{} {} {:.2}\n", v, w, weight))
+        .fold(String::from("8\n16\n"), |text, line| text + &line);
+
+    let g: EdgeWeightedGraph = tiny_ewg.parse().unwrap();
+
+    assert_eq!(g.v(), 8);
+    assert_eq!(g.e(), 16);
+    assert_eq!(PrimMST::new(&g).weight(), 1.81);
+
+    assert_eq!("8\n1\n0 1 x".parse::<EdgeWeightedGraph>().err(), Some(ParseError::InvalidWeight { line: 3, text: String::from("x") }));
+    assert_eq!("8\n1\n0 8 0.5".parse::<EdgeWeightedGraph>().err(), Some(ParseError::VertexOutOfRange { line: 3, vertex: 8, v: 8 }));
+    assert_eq!("8\n1\n0 1".parse::<EdgeWeightedGraph>().err(), Some(ParseError::InvalidEdge { line: 3, text: String::from("0 1") }));
 }
\ No newline at end of file
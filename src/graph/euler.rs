@@ -0,0 +1,173 @@
+use super::graph::Graph;
+
+// 欧拉路径和欧拉环，使用 Hierholzer 算法
+pub struct EulerianPath {
+    path: Option<Vec<usize>>,   // 经过每条边恰好一次的路径
+    odd: usize,                 // 奇数度数顶点的数量
+}
+
+impl EulerianPath {
+    pub fn new(g: &Graph) -> Self {
+        let mut this = EulerianPath {
+            path: None,
+            odd: 0,
+        };
+
+        // 起点优先选择奇数度数的顶点，否则选择任意有边的顶点
+        let mut s = None;
+
+        for v in 0..g.v() {
+            if g.degree(v) % 2 == 1 {
+                this.odd += 1;
+                s = Some(v);
+            }
+            else if s.is_none() && g.degree(v) > 0 {
+                s = Some(v);
+            }
+        }
+
+        if this.odd > 2 {
+            return this
+        }
+
+        match s {
+            Some(s) => this.path = Self::hierholzer(g, s),
+            // 没有边的图，路径只包含一个顶点
+            None if g.v() > 0 => this.path = Some(vec![0]),
+            None => {},
+        }
+
+        this
+    }
+
+    fn hierholzer(g: &Graph, s: usize) -> Option<Vec<usize>> {
+        // 复制邻接表，为每条边编号，无向边在两个顶点的邻接表中共用一个编号
+        let mut adj: Vec<Vec<(usize, usize)>> = vec![Vec::new(); g.v()];
+        let mut m = 0;
+
+        for v in 0..g.v() {
+            let mut self_loops = 0;
+
+            for &w in g.adj(v) {
+                if v < w {
+                    adj[v].push((w, m));
+                    adj[w].push((v, m));
+                    m += 1;
+                }
+                else if v == w {
+                    // 自环在邻接表中出现两次，只记录一次
+                    if self_loops % 2 == 0 {
+                        adj[v].push((v, m));
+                        m += 1;
+                    }
+
+                    self_loops += 1;
+                }
+            }
+        }
+
+        let mut used = vec![false; m];
+        let mut next = vec![0; g.v()];
+        let mut stack = vec![s];
+        let mut path = Vec::with_capacity(m + 1);
+
+        while let Some(&v) = stack.last() {
+            // 跳过已经走过的边
+            while next[v] < adj[v].len() && used[adj[v][next[v]].1] {
+                next[v] += 1;
+            }
+
+            if next[v] == adj[v].len() {
+                path.push(v);
+                stack.pop();
+            }
+            else {
+                let (w, id) = adj[v][next[v]];
+                used[id] = true;
+                stack.push(w);
+            }
+        }
+
+        // 有边不连通时无法走完所有边
+        if path.len() == m + 1 {
+            path.reverse();
+            Some(path)
+        }
+        else {
+            None
+        }
+    }
+
+    pub fn has_eulerian_path(&self) -> bool {
+        self.path.is_some()
+    }
+
+    pub fn has_eulerian_circuit(&self) -> bool {
+        self.path.is_some() && self.odd == 0
+    }
+
+    pub fn path(&self) -> Option<Vec<usize>> {
+        self.path.clone()
+    }
+}
+
+
+#[test]
+fn test() {
+    // 检查路径恰好经过每条边一次
+    fn edges_of(path: &[usize]) -> Vec<(usize, usize)> {
+        let mut edges: Vec<(usize, usize)> = path.windows(2)
+            .map(|pair| (pair[0].min(pair[1]), pair[0].max(pair[1])))
+            .collect();
+        edges.sort();
+        edges
+    }
+
+    // 两个三角形共用顶点 0，每个顶点的度数都是偶数
+    let bowtie = [(0, 1), (1, 2), (2, 0), (0, 3), (3, 4), (4, 0)];
+    let mut g = Graph::with_capacity(5);
+
+    for &(v, w) in bowtie.iter() {
+        g.add_edge(v, w);
+    }
+
+    let euler = EulerianPath::new(&g);
+    assert!(euler.has_eulerian_path());
+    assert!(euler.has_eulerian_circuit());
+
+    let path = euler.path().unwrap();
+    assert_eq!(path.len(), g.e() + 1);
+    assert_eq!(path.first(), path.last());
+
+    let mut expected: Vec<(usize, usize)> = bowtie.iter().map(|&(v, w)| (v.min(w), v.max(w))).collect();
+    expected.sort();
+    assert_eq!(edges_of(&path), expected);
+
+    // 两个奇数度数的顶点，只有欧拉路径
+    let mut g = Graph::with_capacity(3);
+    g.add_edge(0, 1);
+    g.add_edge(1, 2);
+
+    let euler = EulerianPath::new(&g);
+    assert!(! euler.has_eulerian_circuit());
+    assert_eq!(euler.path(), Some(vec![2, 1, 0]));
+
+    // 四个奇数度数的顶点
+    let mut g = Graph::with_capacity(4);
+    g.add_edge(0, 1);
+    g.add_edge(0, 2);
+    g.add_edge(0, 3);
+
+    let euler = EulerianPath::new(&g);
+    assert!(! euler.has_eulerian_path());
+    assert_eq!(euler.path(), None);
+
+    // 度数都是偶数但是不连通
+    let mut g = Graph::with_capacity(6);
+
+    for &(v, w) in [(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3)].iter() {
+        g.add_edge(v, w);
+    }
+
+    assert_eq!(EulerianPath::new(&g).path(), None);
+}
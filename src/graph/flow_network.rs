@@ -0,0 +1,294 @@
+use std::collections::VecDeque;
+use std::f32;
+
+// 剩余容量不超过该值时视为 0，避免浮点误差导致无效的增广
+const FLOATING_POINT_EPSILON: f32 = 1e-6;
+
+// 流量网络中的边，v -> w
+#[derive(Debug, Clone)]
+pub struct FlowEdge {
+    v: usize,
+    w: usize,
+    capacity: f32,
+    flow: f32,
+}
+
+impl FlowEdge {
+    pub fn new(v: usize, w: usize, capacity: f32) -> Self {
+        FlowEdge { v, w, capacity, flow: 0.0 }
+    }
+
+    pub fn from(&self) -> usize {
+        self.v
+    }
+
+    pub fn to(&self) -> usize {
+        self.w
+    }
+
+    pub fn capacity(&self) -> f32 {
+        self.capacity
+    }
+
+    pub fn flow(&self) -> f32 {
+        self.flow
+    }
+
+    // 给定一个顶点，返回另一个，v 不是这条边的顶点时返回 None
+    pub fn other(&self, v: usize) -> Option<usize> {
+        if v == self.v {
+            Some(self.w)
+        }
+        else if v == self.w {
+            Some(self.v)
+        }
+        else {
+            None
+        }
+    }
+
+    // 流向 v 的剩余容量：正向为未使用的容量，反向为可以退回的流量
+    pub fn residual_capacity_to(&self, v: usize) -> f32 {
+        if v == self.w {
+            self.capacity - self.flow
+        }
+        else if v == self.v {
+            self.flow
+        }
+        else {
+            panic!("{} is not an endpoint of {}->{}", v, self.v, self.w)
+        }
+    }
+
+    // 向 v 增加 delta 的流量，反向时减少边上的流量
+    pub fn add_residual_flow_to(&mut self, v: usize, delta: f32) {
+        if v == self.w {
+            self.flow += delta;
+        }
+        else if v == self.v {
+            self.flow -= delta;
+        }
+        else {
+            panic!("{} is not an endpoint of {}->{}", v, self.v, self.w)
+        }
+    }
+}
+
+// 流量网络，邻接表中保存边的编号，每条边同时出现在两个顶点的邻接表中
+pub struct FlowNetwork {
+    v: usize,
+    edges: Vec<FlowEdge>,
+    adj: Vec<Vec<usize>>,
+}
+
+impl FlowNetwork {
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut this = FlowNetwork {
+            v: capacity,
+            edges: Vec::new(),
+            adj: Vec::with_capacity(capacity),
+        };
+
+        for _ in 0..capacity {
+            this.adj.push(Vec::new());
+        }
+
+        this
+    }
+
+    // 给定顶点数量和 (v, w, capacity) 形式的边，初始化网络
+    pub fn from_edges<I: IntoIterator<Item = (usize, usize, f32)>>(capacity: usize, edges: I) -> Self {
+        let mut this = FlowNetwork::with_capacity(capacity);
+
+        for (v, w, capacity) in edges {
+            this.add_edge(FlowEdge::new(v, w, capacity));
+        }
+
+        this
+    }
+
+    pub fn v(&self) -> usize {
+        self.v
+    }
+
+    pub fn e(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn add_edge(&mut self, edge: FlowEdge) {
+        let i = self.edges.len();
+
+        self.adj[edge.from()].push(i);
+        self.adj[edge.to()].push(i);
+        self.edges.push(edge);
+    }
+
+    // 与 v 相连的边的编号
+    pub fn adj(&self, v: usize) -> &Vec<usize> {
+        &self.adj[v]
+    }
+
+    pub fn edge(&self, i: usize) -> &FlowEdge {
+        &self.edges[i]
+    }
+
+    pub fn edges(&self) -> &Vec<FlowEdge> {
+        &self.edges
+    }
+}
+
+// 最大流（Ford-Fulkerson），每次用广度优先搜索找最短的增广路径
+pub struct FordFulkerson {
+    marked: Vec<bool>,              // 剩余网络中 s 到 v 是否可达
+    edge_to: Vec<Option<usize>>,    // s 到 v 的增广路径上的最后一条边
+    value: f32,
+}
+
+impl FordFulkerson {
+    // 计算结束后网络中每条边的流量就是最大流
+    pub fn new(net: &mut FlowNetwork, s: usize, t: usize) -> Self {
+        assert!(s < net.v(), "vertex {} out of range, network has {} vertices", s, net.v());
+        assert!(t < net.v(), "vertex {} out of range, network has {} vertices", t, net.v());
+        assert!(s != t, "source and sink must be different, both are {}", s);
+
+        let mut this = FordFulkerson {
+            marked: vec![false; net.v()],
+            edge_to: vec![None; net.v()],
+            value: 0.0,
+        };
+
+        while this.has_augmenting_path(net, s, t) {
+            // 路径上的最小剩余容量
+            let mut bottle = f32::INFINITY;
+            let mut v = t;
+
+            while let Some(i) = this.edge_to[v] {
+                let edge = net.edge(i);
+                bottle = bottle.min(edge.residual_capacity_to(v));
+                v = edge.other(v).unwrap();
+            }
+
+            let mut v = t;
+
+            while let Some(i) = this.edge_to[v] {
+                let edge = &mut net.edges[i];
+                edge.add_residual_flow_to(v, bottle);
+                v = edge.other(v).unwrap();
+            }
+
+            this.value += bottle;
+        }
+
+        this
+    }
+
+    // 最大流的值
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    // v 是否在最小切分中 s 的一侧
+    pub fn in_cut(&self, v: usize) -> bool {
+        self.marked[v]
+    }
+
+    fn has_augmenting_path(&mut self, net: &FlowNetwork, s: usize, t: usize) -> bool {
+        self.marked = vec![false; net.v()];
+        self.edge_to = vec![None; net.v()];
+
+        let mut queue = VecDeque::new();
+        self.marked[s] = true;
+        queue.push_back(s);
+
+        while let Some(v) = queue.pop_front() {
+            for &i in net.adj(v) {
+                let edge = net.edge(i);
+                let w = edge.other(v).unwrap();
+
+                if edge.residual_capacity_to(w) > FLOATING_POINT_EPSILON && ! self.marked[w] {
+                    self.edge_to[w] = Some(i);
+                    self.marked[w] = true;
+                    queue.push_back(w);
+                }
+            }
+        }
+
+        self.marked[t]
+    }
+}
+
+
+#[test]
+fn test() {
+    let mut net = FlowNetwork::from_edges(6, vec![
+        (0, 1, 2.0), (0, 2, 3.0), (1, 3, 3.0), (1, 4, 1.0),
+        (2, 3, 1.0), (2, 4, 1.0), (3, 5, 2.0), (4, 5, 3.0),
+    ]);
+
+    let ff = FordFulkerson::new(&mut net, 0, 5);
+
+    assert_eq!(ff.value(), 4.0);
+
+    let cut: Vec<usize> = (0..net.v()).filter(|&v| ff.in_cut(v)).collect();
+    assert_eq!(cut, [0, 2]);
+
+    // 跨越切分的边都已饱和，容量之和等于最大流
+    let mut cut_capacity = 0.0;
+
+    for edge in net.edges() {
+        assert!(edge.flow() >= 0.0 && edge.flow() <= edge.capacity());
+
+        if ff.in_cut(edge.from()) && ! ff.in_cut(edge.to()) {
+            assert_eq!(edge.flow(), edge.capacity());
+            cut_capacity += edge.capacity();
+        }
+    }
+
+    assert_eq!(cut_capacity, ff.value());
+
+    // 除起点和终点外流量守恒
+    for v in 1..5 {
+        let inflow: f32 = net.edges().iter().filter(|edge| edge.to() == v).map(|edge| edge.flow()).sum();
+        let outflow: f32 = net.edges().iter().filter(|edge| edge.from() == v).map(|edge| edge.flow()).sum();
+        assert_eq!(inflow, outflow);
+    }
+}
+
+#[test]
+fn test_flow_edge() {
+    let mut edge = FlowEdge::new(0, 1, 5.0);
+
+    assert_eq!(edge.residual_capacity_to(1), 5.0);
+    assert_eq!(edge.residual_capacity_to(0), 0.0);
+
+    edge.add_residual_flow_to(1, 3.0);
+    assert_eq!(edge.flow(), 3.0);
+    assert_eq!(edge.residual_capacity_to(1), 2.0);
+    assert_eq!(edge.residual_capacity_to(0), 3.0);
+
+    edge.add_residual_flow_to(0, 1.0);
+    assert_eq!(edge.flow(), 2.0);
+    assert_eq!(edge.other(0), Some(1));
+    assert_eq!(edge.other(2), None);
+}
+
+#[test]
+#[should_panic(expected = "source and sink must be different, both are 0")]
+fn test_same_source_and_sink() {
+    let mut net = FlowNetwork::from_edges(2, vec![(0, 1, 1.0)]);
+    FordFulkerson::new(&mut net, 0, 0);
+}
+
+#[test]
+fn test_round_off() {
+    // 容量在 f32 中不能精确表示，结果只在误差范围内相等
+    let mut net = FlowNetwork::from_edges(4, vec![
+        (0, 1, 0.1), (0, 2, 0.2), (1, 3, 0.3), (2, 3, 0.3), (1, 2, 0.1),
+    ]);
+
+    let ff = FordFulkerson::new(&mut net, 0, 3);
+
+    assert!((ff.value() - 0.3).abs() < 1e-6);
+    assert!(ff.in_cut(0));
+    assert!(! ff.in_cut(3));
+}
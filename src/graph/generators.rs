@@ -0,0 +1,77 @@
+use super::graph::Graph;
+
+// 伪随机数生成器 splitmix64，相同的种子生成相同的序列
+struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    fn new(seed: u64) -> Self {
+        SplitMix64 { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // [0, 1) 之间的浮点数
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+// 随机图 G(v, p)：任意两个顶点之间以概率 p 相连
+pub fn erdos_renyi(v: usize, edge_prob: f64, seed: u64) -> Graph {
+    let mut rng = SplitMix64::new(seed);
+    let mut g = Graph::with_capacity(v);
+
+    for i in 0..v {
+        for j in i + 1..v {
+            if rng.next_f64() < edge_prob {
+                g.add_edge(i, j);
+            }
+        }
+    }
+
+    g
+}
+
+// 完全图：任意两个顶点之间都相连
+pub fn complete(v: usize) -> Graph {
+    erdos_renyi(v, 1.0, 0)
+}
+
+#[test]
+fn test_complete() {
+    let g = complete(5);
+
+    assert_eq!(g.v(), 5);
+    assert_eq!(g.e(), 10);
+    assert_eq!(g.max_degree(), 4);
+    assert_eq!(g.number_of_self_loops(), 0);
+    assert_eq!(complete(0).e(), 0);
+}
+
+#[test]
+fn test_erdos_renyi() {
+    let g = erdos_renyi(50, 0.2, 42);
+    let h = erdos_renyi(50, 0.2, 42);
+
+    assert_eq!(g.v(), 50);
+    assert_eq!(g.e(), h.e());
+
+    for v in 0..g.v() {
+        assert_eq!(g.adj(v), h.adj(v));
+    }
+
+    // 期望边数为 0.2 * 50 * 49 / 2 = 245
+    assert!(g.e() > 150 && g.e() < 350);
+
+    assert_eq!(erdos_renyi(10, 0.0, 7).e(), 0);
+    assert_eq!(erdos_renyi(10, 1.0, 7).e(), 45);
+}
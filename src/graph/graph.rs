@@ -1,4 +1,9 @@
 use std::fmt;
+use std::mem;
+use std::str::FromStr;
+use std::collections::BTreeSet;
+use super::cc::CC;
+use super::cycle::Cycle;
 
 // 图
 pub struct Graph {
@@ -23,6 +28,17 @@ impl Graph {
         this
     }
 
+    // 给定顶点数量和边，初始化图
+    pub fn from_edges<I: IntoIterator<Item = (usize, usize)>>(capacity: usize, edges: I) -> Self {
+        let mut this = Graph::with_capacity(capacity);
+
+        for (v, w) in edges {
+            this.add_edge(v, w);
+        }
+
+        this
+    }
+
     // 顶点数量
     pub fn v(&self) -> usize {
         self.v
@@ -33,8 +49,11 @@ impl Graph {
         self.e
     }
 
-    // 增加边
+    // 增加边，顶点数量在 with_capacity 时已经确定，顶点越界时直接 panic，不会自动扩容
     pub fn add_edge(&mut self, v: usize, w: usize) {
+        self.validate_vertex(v);
+        self.validate_vertex(w);
+
         // 避免平行边和自环
         if (! self.adj[v].contains(&w) && ! self.adj[w].contains(&v)) || (v != w) {
             self.adj[v].push(w);
@@ -43,11 +62,39 @@ impl Graph {
         }
     }
 
+    // 增加边，拒绝自环和平行边，返回是否添加成功
+    pub fn add_edge_checked(&mut self, v: usize, w: usize) -> bool {
+        self.validate_vertex(v);
+        self.validate_vertex(w);
+
+        if v == w || self.adj[v].contains(&w) {
+            return false
+        }
+
+        self.add_edge(v, w);
+
+        true
+    }
+
+    fn validate_vertex(&self, v: usize) {
+        assert!(v < self.v, "vertex {} out of range, graph has {} vertices", v, self.v);
+    }
+
     // 顶点指向的顶点
     pub fn adj(&self, v: usize) -> &Vec<usize> {
         &self.adj[v]
     }
 
+    // 顶点是否存在
+    pub fn contains_vertex(&self, v: usize) -> bool {
+        v < self.v
+    }
+
+    // 两个顶点之间是否有边，与顶点顺序无关
+    pub fn contains_edge(&self, v: usize, w: usize) -> bool {
+        self.contains_vertex(v) && self.contains_vertex(w) && self.adj[v].contains(&w)
+    }
+
     // 顶点度数
     pub fn degree(&self, v: usize) -> usize {
         self.adj(v).len()
@@ -85,8 +132,263 @@ impl Graph {
 
         count / 2
     }
+
+    // 邻接矩阵，相邻为 1，自环在对角线上
+    pub fn to_adjacency_matrix(&self) -> Vec<Vec<u8>> {
+        let mut matrix = vec![vec![0; self.v()]; self.v()];
+
+        for (v, row) in matrix.iter_mut().enumerate() {
+            for &w in self.adj(v) {
+                row[w] = 1;
+            }
+        }
+
+        matrix
+    }
+
+    // 导出子图，只保留两个顶点都在 vertices 中的边
+    // 子图的顶点重新编号为 0..vertices.len()，子图中的顶点 i 对应原图中的 vertices[i]
+    pub fn subgraph(&self, vertices: &[usize]) -> Graph {
+        let mut index = vec![None; self.v()];
+
+        for (i, &v) in vertices.iter().enumerate() {
+            self.validate_vertex(v);
+            assert!(index[v].is_none(), "vertex {} appears more than once", v);
+            index[v] = Some(i);
+        }
+
+        let mut g = Graph::with_capacity(vertices.len());
+
+        for (i, &v) in vertices.iter().enumerate() {
+            for &w in self.adj(v) {
+                match index[w] {
+                    Some(j) if i <= j => g.add_edge(i, j),
+                    _ => {},
+                }
+            }
+        }
+
+        g
+    }
+
+    // 收缩边：将 w 合并到 v，w 的边改为连接 v，v 和 w 之间的边（包括平行边）以及 w 的自环被删除
+    // 保留产生的平行边（多重图，Karger 算法需要），w 成为孤立顶点，顶点数量不变
+    pub fn contract_edge(&mut self, v: usize, w: usize) {
+        self.validate_vertex(v);
+        self.validate_vertex(w);
+        assert!(v != w, "cannot contract a self-loop at {}", v);
+
+        let adj = mem::take(&mut self.adj[w]);
+        let mut self_loops = 0;
+
+        for x in adj {
+            if x == v {
+                let p = self.adj[v].iter().position(|&y| y == w).unwrap();
+                self.adj[v].swap_remove(p);
+                self.e -= 1;
+            }
+            else if x == w {
+                // 自环在邻接表中出现两次
+                self_loops += 1;
+            }
+            else {
+                let p = self.adj[x].iter().position(|&y| y == w).unwrap();
+                self.adj[x][p] = v;
+                self.adj[v].push(x);
+            }
+        }
+
+        self.e -= self_loops / 2;
+    }
+
+    // 森林：无环
+    pub fn is_forest(&self) -> bool {
+        ! Cycle::new(self).has_cycle()
+    }
+
+    // 树：连通、无环，边数为顶点数减一
+    pub fn is_tree(&self) -> bool {
+        self.v() > 0 && self.e() + 1 == self.v() && CC::new(self).count() == 1 && self.is_forest()
+    }
+
+    // 补图：顶点相同，原图中不相邻的两个顶点在补图中相邻，不含自环
+    pub fn complement(&self) -> Graph {
+        let mut g = Graph::with_capacity(self.v());
+
+        for v in 0..self.v() {
+            for w in v + 1..self.v() {
+                if ! self.adj(v).contains(&w) {
+                    g.add_edge(v, w);
+                }
+            }
+        }
+
+        g
+    }
+
+    // k 核：反复删除度数小于 k 的顶点，删除会使相邻顶点的度数减小，直到剩余顶点的度数都不小于 k
+    // 返回剩余的顶点，由小到大排列
+    pub fn k_core(&self, k: usize) -> Vec<usize> {
+        let mut degree: Vec<usize> = (0..self.v()).map(|v| self.degree(v)).collect();
+        let mut removed = vec![false; self.v()];
+        let mut stack = Vec::new();
+
+        for v in 0..self.v() {
+            if degree[v] < k {
+                removed[v] = true;
+                stack.push(v);
+            }
+        }
+
+        while let Some(v) = stack.pop() {
+            for &w in self.adj(v) {
+                if removed[w] {
+                    continue
+                }
+
+                degree[w] -= 1;
+
+                if degree[w] < k {
+                    removed[w] = true;
+                    stack.push(w);
+                }
+            }
+        }
+
+        (0..self.v()).filter(|&v| ! removed[v]).collect()
+    }
+
+    // 退化序：反复删除度数最小的顶点（度数相同时取编号小的），返回删除的顺序和退化度
+    // 退化度是删除时度数的最大值，也就是最大的核数，每个顶点在序列中之后的相邻顶点不超过这个数
+    pub fn degeneracy_ordering(&self) -> (Vec<usize>, usize) {
+        let mut degree: Vec<usize> = (0..self.v()).map(|v| self.degree(v)).collect();
+        let mut removed = vec![false; self.v()];
+        let mut pq: BTreeSet<(usize, usize)> = (0..self.v()).map(|v| (degree[v], v)).collect();
+        let mut order = Vec::with_capacity(self.v());
+        let mut degeneracy = 0;
+
+        while let Some(&(d, v)) = pq.iter().next() {
+            pq.remove(&(d, v));
+            removed[v] = true;
+            order.push(v);
+            degeneracy = degeneracy.max(d);
+
+            for &w in self.adj(v) {
+                if ! removed[w] {
+                    pq.remove(&(degree[w], w));
+                    degree[w] -= 1;
+                    pq.insert((degree[w], w));
+                }
+            }
+        }
+
+        (order, degeneracy)
+    }
+}
+
+
+#[derive(Debug, PartialEq)]
+pub enum ParseError {
+    MissingVertexCount,
+    MissingEdgeCount,
+    InvalidNumber { line: usize, text: String },
+    InvalidEdge { line: usize, text: String },
+    InvalidWeight { line: usize, text: String },
+    VertexOutOfRange { line: usize, vertex: usize, v: usize },
+    EdgeCountMismatch { expected: usize, found: usize },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseError::MissingVertexCount => write!(f, "missing vertex count"),
+            ParseError::MissingEdgeCount => write!(f, "missing edge count"),
+            ParseError::InvalidNumber { line, ref text } => {
+                write!(f, "line {}: expected a number, found {:?}", line, text)
+            },
+            ParseError::InvalidEdge { line, ref text } => {
+                write!(f, "line {}: malformed edge {:?}", line, text)
+            },
+            ParseError::InvalidWeight { line, ref text } => {
+                write!(f, "line {}: expected a weight, found {:?}", line, text)
+            },
+            ParseError::VertexOutOfRange { line, vertex, v } => {
+                write!(f, "line {}: vertex {} out of range, graph has {} vertices", line, vertex, v)
+            },
+            ParseError::EdgeCountMismatch { expected, found } => {
+                write!(f, "expected {} edges, found {}", expected, found)
+            },
+        }
+    }
+}
+
+// 顶点数和所有边 (v, w, weight)
+pub(crate) type ParsedEdges = (usize, Vec<(usize, usize, f32)>);
+
+// 解析 "顶点数、边数、每行一条边" 格式的文本
+// weighted 为 true 时每行为 "v w weight"，否则为 "v w"，此时权重为 0
+pub(crate) fn parse_edges(input: &str, weighted: bool) -> Result<ParsedEdges, ParseError> {
+    let mut lines = input.lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|&(_, line)| ! line.is_empty());
+
+    let parse_number = |(line, text): (usize, &str)| {
+        text.parse::<usize>().map_err(|_| ParseError::InvalidNumber { line, text: String::from(text) })
+    };
+
+    let v = parse_number(lines.next().ok_or(ParseError::MissingVertexCount)?)?;
+    let e = parse_number(lines.next().ok_or(ParseError::MissingEdgeCount)?)?;
+    let mut edges = Vec::with_capacity(e);
+
+    for (line, text) in lines {
+        let fields: Vec<&str> = text.split_whitespace().collect();
+
+        if fields.len() != if weighted { 3 } else { 2 } {
+            return Err(ParseError::InvalidEdge { line, text: String::from(text) })
+        }
+
+        let a = parse_number((line, fields[0]))?;
+        let b = parse_number((line, fields[1]))?;
+        let weight = if weighted {
+            fields[2].parse::<f32>()
+                .map_err(|_| ParseError::InvalidWeight { line, text: String::from(fields[2]) })?
+        }
+        else {
+            0.0
+        };
+
+        for &vertex in [a, b].iter() {
+            if vertex >= v {
+                return Err(ParseError::VertexOutOfRange { line, vertex, v })
+            }
+        }
+
+        edges.push((a, b, weight));
+    }
+
+    if edges.len() != e {
+        return Err(ParseError::EdgeCountMismatch { expected: e, found: edges.len() })
+    }
+
+    Ok((v, edges))
 }
 
+// 文本格式：第一行为顶点数量，第二行为边数量，之后每行一条边 "v w"，忽略空行
+impl FromStr for Graph {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let (v, edges) = parse_edges(input, false)?;
+        let mut g = Graph::with_capacity(v);
+
+        for (a, b, _) in edges {
+            g.add_edge(a, b);
+        }
+
+        Ok(g)
+    }
+}
 
 impl fmt::Debug for Graph {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -119,3 +421,257 @@ fn test() {
     assert_eq!(g.e(), 13);
     assert_eq!(g.adj(0), &[5, 1, 2, 6])
 }
+
+
+#[test]
+fn test_with_capacity() {
+    let mut g = Graph::with_capacity(4);
+
+    g.add_edge(0, 1);
+    g.add_edge(2, 3);
+    g.add_edge(3, 0);
+
+    assert_eq!(g.v(), 4);
+    assert_eq!(g.e(), 3);
+    assert_eq!(g.adj(3), &[2, 0]);
+}
+
+#[test]
+#[should_panic(expected = "vertex 4 out of range, graph has 4 vertices")]
+fn test_add_edge_out_of_range() {
+    let mut g = Graph::with_capacity(4);
+    g.add_edge(0, 4);
+}
+
+#[test]
+fn test_from_edges() {
+    let tiny_cg = [
+        (0, 5), (2, 4), (2, 3), (1, 2), (0, 1), (3, 4), (3, 5), (0, 2),
+    ];
+
+    let g = Graph::from_edges(6, tiny_cg.iter().cloned());
+
+    assert_eq!(g.v(), 6);
+    assert_eq!(g.e(), 8);
+    assert_eq!(g.adj(0), &[5, 1, 2]);
+}
+
+#[test]
+fn test_add_edge_checked() {
+    let mut g = Graph::with_capacity(3);
+
+    assert!(g.add_edge_checked(0, 1));
+    assert!(g.add_edge_checked(1, 2));
+
+    // 自环
+    assert!(! g.add_edge_checked(2, 2));
+
+    // 平行边，两个方向都算
+    assert!(! g.add_edge_checked(0, 1));
+    assert!(! g.add_edge_checked(1, 0));
+
+    assert_eq!(g.e(), 2);
+    assert_eq!(g.adj(1), &[0, 2]);
+    assert_eq!(g.number_of_self_loops(), 0);
+}
+
+#[test]
+fn test_complement() {
+    let g = Graph::from_edges(4, vec![(0, 1), (1, 2), (2, 0)]);
+    let c = g.complement();
+
+    assert_eq!(c.v(), 4);
+    assert_eq!(c.e(), 3);
+    assert_eq!(c.adj(3), &[0, 1, 2]);
+    assert_eq!(c.number_of_self_loops(), 0);
+
+    // 补图的边恰好是原图中不存在的边
+    for v in 0..g.v() {
+        for w in 0..g.v() {
+            if v != w {
+                assert_eq!(c.adj(v).contains(&w), ! g.adj(v).contains(&w));
+            }
+        }
+    }
+
+    // 补图的补图是原图
+    assert_eq!(c.complement().e(), g.e());
+}
+
+#[test]
+fn test_contains() {
+    let g = Graph::from_edges(4, vec![(0, 1), (1, 2)]);
+
+    assert!(g.contains_vertex(3));
+    assert!(! g.contains_vertex(4));
+
+    assert!(g.contains_edge(0, 1));
+    assert!(g.contains_edge(1, 0));
+    assert!(g.contains_edge(2, 1));
+    assert!(! g.contains_edge(0, 2));
+    assert!(! g.contains_edge(3, 3));
+    assert!(! g.contains_edge(0, 4));
+}
+
+#[test]
+fn test_to_adjacency_matrix() {
+    let g = Graph::from_edges(4, vec![(0, 1), (1, 2), (2, 3), (3, 3)]);
+    let matrix = g.to_adjacency_matrix();
+
+    assert_eq!(matrix, [
+        [0, 1, 0, 0],
+        [1, 0, 1, 0],
+        [0, 1, 0, 1],
+        [0, 0, 1, 1],
+    ]);
+
+    for (i, row) in matrix.iter().enumerate() {
+        for (j, &cell) in row.iter().enumerate() {
+            assert_eq!(cell, matrix[j][i]);
+        }
+    }
+}
+
+#[test]
+fn test_subgraph() {
+    let tiny_g = [
+        (0, 5), (4, 3), (0, 1), (9, 12), (6, 4), (5, 4), (0, 2),
+        (11, 12), (9, 10), (0, 6), (7, 8), (9, 11), (5, 3),
+    ];
+
+    let g = Graph::from_edges(13, tiny_g.iter().cloned());
+
+    // 0 -> 0, 5 -> 1, 4 -> 2
+    let sub = g.subgraph(&[0, 5, 4]);
+
+    assert_eq!(sub.v(), 3);
+    assert_eq!(sub.e(), 2);
+    assert_eq!(sub.adj(0), &[1]);
+    assert_eq!(sub.adj(1), &[0, 2]);
+    assert_eq!(sub.adj(2), &[1]);
+
+    assert_eq!(g.subgraph(&[7, 9]).e(), 0);
+    assert_eq!(g.subgraph(&[]).v(), 0);
+}
+
+#[test]
+fn test_is_tree_forest() {
+    // 0 - 1 - 2
+    //      \- 3 - 4
+    let tree = Graph::from_edges(5, vec![(0, 1), (1, 2), (1, 3), (3, 4)]);
+    assert!(tree.is_tree());
+    assert!(tree.is_forest());
+
+    // 两棵树
+    let forest = Graph::from_edges(5, vec![(0, 1), (1, 2), (3, 4)]);
+    assert!(! forest.is_tree());
+    assert!(forest.is_forest());
+
+    // 0 - 1 - 2 - 0
+    let cyclic = Graph::from_edges(4, vec![(0, 1), (1, 2), (2, 0), (2, 3)]);
+    assert!(! cyclic.is_tree());
+    assert!(! cyclic.is_forest());
+
+    assert!(Graph::with_capacity(1).is_tree());
+    assert!(! Graph::with_capacity(0).is_tree());
+}
+
+#[test]
+fn test_from_str() {
+    let tiny_cg = "6\n8\n0 5\n2 4\n2 3\n1 2\n0 1\n3 4\n3 5\n0 2\n";
+    let g: Graph = tiny_cg.parse().unwrap();
+
+    assert_eq!(g.v(), 6);
+    assert_eq!(g.e(), 8);
+    assert_eq!(g.adj(0), &[5, 1, 2]);
+
+    assert_eq!("".parse::<Graph>().unwrap_err(), ParseError::MissingVertexCount);
+    assert_eq!("6".parse::<Graph>().unwrap_err(), ParseError::MissingEdgeCount);
+    assert_eq!("6\nx".parse::<Graph>().unwrap_err(), ParseError::InvalidNumber { line: 2, text: String::from("x") });
+    assert_eq!("6\n1\n0 1 2".parse::<Graph>().unwrap_err(), ParseError::InvalidEdge { line: 3, text: String::from("0 1 2") });
+    assert_eq!("6\n1\n\n0 6".parse::<Graph>().unwrap_err(), ParseError::VertexOutOfRange { line: 4, vertex: 6, v: 6 });
+    assert_eq!("6\n2\n0 1".parse::<Graph>().unwrap_err(), ParseError::EdgeCountMismatch { expected: 2, found: 1 });
+
+    let err = "6\n1\n0 a".parse::<Graph>().unwrap_err();
+    assert_eq!(err.to_string(), "line 3: expected a number, found \"a\"");
+}
+
+#[test]
+fn test_k_core() {
+    // 三角形 0-1-2 与四边形 3-4-5-6 由 2-3 相连，7 和 8 挂在外面，9 孤立
+    let g = Graph::from_edges(10, vec![
+        (0, 1), (1, 2), (2, 0), (2, 3),
+        (3, 4), (4, 5), (5, 6), (6, 3),
+        (1, 7), (7, 8),
+    ]);
+
+    assert_eq!(g.k_core(0).len(), 10);
+    assert_eq!(g.k_core(1), [0, 1, 2, 3, 4, 5, 6, 7, 8]);
+    assert_eq!(g.k_core(2), [0, 1, 2, 3, 4, 5, 6]);
+    assert!(g.k_core(3).is_empty());
+
+    // 删除会逐级传递：路径的 2 核为空
+    let path = Graph::from_edges(5, (0..4).map(|v| (v, v + 1)));
+    assert!(path.k_core(2).is_empty());
+}
+
+#[test]
+fn test_degeneracy_ordering() {
+    let g = Graph::from_edges(10, vec![
+        (0, 1), (1, 2), (2, 0), (2, 3),
+        (3, 4), (4, 5), (5, 6), (6, 3),
+        (1, 7), (7, 8),
+    ]);
+
+    let (order, degeneracy) = g.degeneracy_ordering();
+    assert_eq!(degeneracy, 2);
+    assert_eq!(order.len(), 10);
+    assert_eq!(&order[..3], [9, 8, 7]);
+
+    // 每个顶点在序列中之后的相邻顶点不超过退化度
+    let mut position = vec![0; g.v()];
+
+    for (i, &v) in order.iter().enumerate() {
+        position[v] = i;
+    }
+
+    for v in 0..g.v() {
+        assert!(g.adj(v).iter().filter(|&&w| position[w] > position[v]).count() <= degeneracy);
+    }
+
+    // 完全图 K5 的退化度为 4，树为 1，没有边的图为 0
+    let k5 = Graph::with_capacity(5).complement();
+    assert_eq!(k5.degeneracy_ordering().1, 4);
+    assert_eq!(Graph::from_edges(4, vec![(0, 1), (1, 2), (1, 3)]).degeneracy_ordering().1, 1);
+    assert_eq!(Graph::with_capacity(3).degeneracy_ordering(), (vec![0, 1, 2], 0));
+}
+
+#[test]
+fn test_contract_edge() {
+    let mut g = Graph::from_edges(4, vec![(0, 1), (1, 2), (2, 0), (2, 3), (1, 3)]);
+
+    g.contract_edge(1, 2);
+
+    let mut adj = g.adj(1).clone();
+    adj.sort();
+
+    // 0 和 3 与合并后的顶点之间各有两条平行边
+    assert_eq!(adj, [0, 0, 3, 3]);
+    assert!(g.adj(2).is_empty());
+    assert_eq!(g.adj(0), &vec![1, 1]);
+    assert_eq!(g.e(), 4);
+    assert_eq!(g.number_of_self_loops(), 0);
+
+    // v 和 w 之间的平行边都被删除
+    g.contract_edge(0, 1);
+    assert_eq!(g.adj(0), &vec![3, 3]);
+    assert_eq!(g.adj(3), &vec![0, 0]);
+    assert!(g.adj(1).is_empty());
+    assert_eq!(g.e(), 2);
+
+    // w 的自环被删除
+    let mut g = Graph::from_edges(3, vec![(0, 1), (1, 1), (1, 2)]);
+    g.contract_edge(0, 1);
+    assert_eq!(g.adj(0), &vec![2]);
+    assert_eq!(g.e(), 1);
+}
@@ -3,6 +3,8 @@ use std::collections::BinaryHeap;
 use super::union_find::UnionFind;
 use super::edge::Edge;
 use super::edge_weighted_graph::EdgeWeightedGraph;
+#[cfg(test)]
+use super::edge_weighted_graph::tiny_ewg;
 
 
 pub struct KruskalMST {
@@ -63,18 +65,7 @@ impl KruskalMST {
 
 #[test]
 fn test() {
-    let tiny_ewg = [
-        (4, 5, 0.35), (4, 7, 0.37), (5, 7, 0.28), (0, 7, 0.16),
-        (1, 5, 0.32), (0, 4, 0.38), (2, 3, 0.17), (1, 7, 0.19),
-        (0, 2, 0.26), (1, 2, 0.36), (1, 3, 0.39), (2, 7, 0.34),
-        (6, 2, 0.40), (3, 6, 0.52), (6, 0, 0.58), (6, 4, 0.93),
-    ];
-
-    let mut g = EdgeWeightedGraph::with_capacity(8);
-
-    for &(v, w, weight) in tiny_ewg.iter() {
-        g.add_edge(Edge::new(v, w, weight));
-    }
+    let g = tiny_ewg();
 
     let mst = KruskalMST::new(&g);
 
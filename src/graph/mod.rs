@@ -12,3 +12,13 @@ pub mod edge_weighted_graph;
 pub mod lazy_prim_mst;
 pub mod prim_mst;
 pub mod kruskal_mst;
+pub mod directed_edge;
+pub mod edge_weighted_digraph;
+pub mod acyclic_sp;
+pub mod euler;
+pub mod bipartite_matching;
+pub mod bridge;
+pub mod articulation;
+pub mod cc_bfs;
+pub mod generators;
+pub mod flow_network;
\ No newline at end of file
@@ -1,32 +1,16 @@
 use std::f32;
 use std::rc::Rc;
-use std::cmp::Ordering;
+use std::cmp::Reverse;
 use super::edge::Edge;
 use super::edge_weighted_graph::EdgeWeightedGraph;
+#[cfg(test)]
+use super::edge_weighted_graph::tiny_ewg;
+use super::union_find::UnionFind;
 use super::super::queue::index_binary_heap::IndexBinaryHeap;
+use super::super::queue::ordered_float::OrderedFloat;
 
-// 实现最小索引优先队列，重写 Ord 和 PartialOrd
-#[derive(Eq, PartialEq)]
-struct Weight(u32);
-
-impl Weight {
-    pub fn new(n: f32) -> Self {
-        Weight(n.to_bits())
-    }
-}
-
-impl Ord for Weight {
-    fn cmp(&self, other: &Weight) -> Ordering {
-        other.0.cmp(&self.0)
-    }
-}
-
-impl PartialOrd for Weight {
-    fn partial_cmp(&self, other: &Weight) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
+// 权重反序，实现最小索引优先队列
+type Weight = Reverse<OrderedFloat>;
 
 // 最小生成树 Prim 算法（即时版本）
 pub struct PrimMST {
@@ -34,15 +18,22 @@ pub struct PrimMST {
     dist_to: Vec<f32>,              // 权重
     marked: Vec<bool>,              // 顶点
     pq: IndexBinaryHeap<Weight>,    // 最小索引优先队列
+    weight: f32,                    // 总权重，构造时计算
 }
 
 impl PrimMST {
     pub fn new(g: &EdgeWeightedGraph) -> Self {
+        PrimMST::from_vertex(g, 0)
+    }
+
+    // 从顶点 s 开始，只生成 s 所在连通分量的最小生成树
+    pub fn from_vertex(g: &EdgeWeightedGraph, s: usize) -> Self {
         let mut this = PrimMST {
             edge_to: Vec::with_capacity(g.v()),
             dist_to: Vec::with_capacity(g.v()),
             marked: Vec::with_capacity(g.v()),
             pq: IndexBinaryHeap::with_capacity(g.v()),
+            weight: 0.0,
         };
 
         for _ in 0..g.v() {
@@ -51,14 +42,21 @@ impl PrimMST {
             this.marked.push(false);
         }
 
-        this.dist_to[0] = 0.0;
-        this.pq.put(0, Weight::new(0.0));
+        this.dist_to[s] = 0.0;
+        this.pq.put(s, Reverse(OrderedFloat(0.0)));
 
         while ! this.pq.is_empty() {
             let v = this.pq.pop();
             this.visit(g, v);
         }
 
+        // 与 edges() 的顺序相同，保证浮点数累加的结果一致
+        for e in this.edge_to.iter() {
+            if let Some(ref e) = *e {
+                this.weight += e.weight();
+            }
+        }
+
         this
     }
 
@@ -76,7 +74,7 @@ impl PrimMST {
                 self.edge_to[w] = Some(e.clone());
                 self.dist_to[w] = e.weight();
                 // 有则更新，无则添加
-                self.pq.put(w, Weight::new(e.weight()));
+                self.pq.put(w, Reverse(OrderedFloat(e.weight())));
             }
         }
     }
@@ -93,32 +91,81 @@ impl PrimMST {
         edges
     }
 
+    // 按权重由小到大排列的边，权重相同时按顶点排列
+    pub fn edges_sorted(&self) -> Vec<Rc<Edge>> {
+        let mut edges = self.edges();
+
+        edges.sort_by_key(|e| {
+            let v = e.either();
+            (OrderedFloat(e.weight()), v, e.other(v).unwrap())
+        });
+
+        edges
+    }
+
+    // 验证结果是否为最小生成树，O(EV)，用于测试
+    pub fn check(&self, g: &EdgeWeightedGraph) -> bool {
+        check_mst(g, &self.edges())
+    }
+
     pub fn weight(&self) -> f32 {
-        let mut weight = 0.0;
+        self.weight
+    }
+}
 
-        for edge in self.edges() {
-            weight += edge.weight();
+// 检查 edges 是否为 g 的最小生成树（森林）
+// 无环、覆盖每个连通分量，并满足切分定理：删除任一条树边后，它是横切边中权重最小的
+pub fn check_mst(g: &EdgeWeightedGraph, edges: &[Rc<Edge>]) -> bool {
+    let mut uf = UnionFind::with_capacity(g.v());
+
+    // 无环
+    for e in edges {
+        let v = e.either();
+        let w = e.other(v).unwrap();
+
+        if uf.connected(v, w) {
+            return false
         }
 
-        weight
+        uf.union(v, w);
     }
-}
 
-#[test]
-fn test() {
-    let tiny_ewg = [
-        (4, 5, 0.35), (4, 7, 0.37), (5, 7, 0.28), (0, 7, 0.16),
-        (1, 5, 0.32), (0, 4, 0.38), (2, 3, 0.17), (1, 7, 0.19),
-        (0, 2, 0.26), (1, 2, 0.36), (1, 3, 0.39), (2, 7, 0.34),
-        (6, 2, 0.40), (3, 6, 0.52), (6, 0, 0.58), (6, 4, 0.93),
-    ];
+    // 生成森林，图中每条边的两个顶点都已连通
+    for e in g.edges() {
+        let v = e.either();
 
-    let mut g = EdgeWeightedGraph::with_capacity(8);
+        if ! uf.connected(v, e.other(v).unwrap()) {
+            return false
+        }
+    }
+
+    // 切分定理
+    for e in edges {
+        let mut uf = UnionFind::with_capacity(g.v());
 
-    for &(v, w, weight) in tiny_ewg.iter() {
-        g.add_edge(Edge::new(v, w, weight));
+        for f in edges {
+            if ! Rc::ptr_eq(e, f) {
+                let v = f.either();
+                uf.union(v, f.other(v).unwrap());
+            }
+        }
+
+        for f in g.edges() {
+            let v = f.either();
+
+            if ! uf.connected(v, f.other(v).unwrap()) && f.weight() < e.weight() {
+                return false
+            }
+        }
     }
 
+    true
+}
+
+#[test]
+fn test() {
+    let g = tiny_ewg();
+
     let mst = PrimMST::new(&g);
 
     //    1-7 0.19
@@ -130,4 +177,69 @@ fn test() {
     //    0-7 0.16
     assert_eq!(mst.edges().len(), g.v() - 1);
     assert_eq!(mst.weight(), 1.81);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_edges_sorted() {
+    let g = tiny_ewg();
+    let mst = PrimMST::new(&g);
+    let edges: Vec<String> = mst.edges_sorted().iter().map(|e| e.to_string()).collect();
+
+    assert_eq!(edges[0], "0-7 0.16");
+    assert_eq!(edges, ["0-7 0.16", "2-3 0.17", "1-7 0.19", "0-2 0.26", "5-7 0.28", "4-5 0.35", "6-2 0.40"]);
+}
+
+#[test]
+fn test_check() {
+    let g = tiny_ewg();
+    let mst = PrimMST::new(&g);
+
+    assert!(mst.check(&g));
+
+    // 用 6-4 0.93 代替 4-5 0.35，仍是生成树，但不是最小的
+    let edges: Vec<Rc<Edge>> = g.edges().into_iter().filter(|e| {
+        let s = e.to_string();
+        ["0-7 0.16", "2-3 0.17", "1-7 0.19", "0-2 0.26", "5-7 0.28", "6-2 0.40", "6-4 0.93"].contains(&s.as_str())
+    }).collect();
+
+    assert_eq!(edges.len(), 7);
+    assert!(! check_mst(&g, &edges));
+
+    // 少一条边，不是生成树
+    assert!(! check_mst(&g, &mst.edges()[1..]));
+}
+
+#[test]
+fn test_from_vertex() {
+    let g = tiny_ewg();
+    let mst = PrimMST::from_vertex(&g, 3);
+
+    assert_eq!(mst.weight(), PrimMST::new(&g).weight());
+    assert_eq!(mst.weight(), 1.81);
+    assert_eq!(mst.edges().len(), g.v() - 1);
+    assert!(mst.check(&g));
+
+    for s in 0..g.v() {
+        assert_eq!(PrimMST::from_vertex(&g, s).edges_sorted().len(), 7);
+    }
+}
+
+#[test]
+fn test_weight_cached() {
+    let g = tiny_ewg();
+    let mst = PrimMST::new(&g);
+    let edges = mst.edges();
+
+    assert_eq!(mst.weight(), 1.81);
+    assert_eq!(mst.weight(), 1.81);
+
+    // 与按 edges() 顺序累加的结果完全相同
+    let mut weight = 0.0;
+
+    for edge in edges.iter() {
+        weight += edge.weight();
+    }
+
+    assert_eq!(mst.weight(), weight);
+    assert_eq!(mst.edges(), edges);
+}
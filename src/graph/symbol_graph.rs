@@ -48,6 +48,28 @@ impl<'a> SymbolGraph<'a> {
         SymbolGraph { st, keys, g }
     }
 
+    // 由符号名对构建，每一对为一条边
+    pub fn from_pairs(pairs: &[(&'a str, &'a str)]) -> Self {
+        SymbolGraph::new(pairs.iter().map(|&(v, w)| vec![v, w]).collect())
+    }
+
+    // 由分隔的文本行构建，例如 "电影/演员1/演员2"，第一个字段与其余字段相连
+    pub fn from_lines(lines: &[&'a str], delimiter: char) -> Self {
+        let mut data = Vec::new();
+
+        for line in lines {
+            let mut fields = line.split(delimiter);
+
+            if let Some(first) = fields.next() {
+                for other in fields {
+                    data.push(vec![first, other]);
+                }
+            }
+        }
+
+        SymbolGraph::new(data)
+    }
+
     pub fn contains(&self, s: &str) -> bool {
         self.st.contains_key(s)
     }
@@ -107,4 +129,55 @@ fn test() {
     assert_eq!(symbol_graph.name(10), None);
     
     assert_eq!(symbol_graph.g().v(), 10)
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_from_pairs() {
+    let pairs = [("JFK", "MCO"), ("ORD", "DEN"), ("JFK", "ORD"), ("DEN", "MCO")];
+    let symbol_graph = SymbolGraph::from_pairs(&pairs);
+
+    assert_eq!(symbol_graph.g().v(), 4);
+    assert_eq!(symbol_graph.g().e(), 4);
+
+    for name in ["JFK", "MCO", "ORD", "DEN"].iter() {
+        let v = *symbol_graph.index(name).unwrap();
+        assert_eq!(symbol_graph.name(v), Some(*name));
+    }
+
+    let jfk = *symbol_graph.index("JFK").unwrap();
+    let ord = *symbol_graph.index("ORD").unwrap();
+    assert!(symbol_graph.g().adj(jfk).contains(&ord));
+}
+
+#[test]
+fn test_from_lines() {
+    let lines = [
+        "Animal House (1978)/Bacon, Kevin/Belushi, John/Matheson, Tim",
+        "Apollo 13 (1995)/Bacon, Kevin/Hanks, Tom/Paxton, Bill",
+        "Big (1988)/Hanks, Tom/Perkins, Elizabeth",
+    ];
+
+    let symbol_graph = SymbolGraph::from_lines(&lines, '/');
+    let g = symbol_graph.g();
+
+    // 3 部电影，6 位演员
+    assert_eq!(g.v(), 9);
+    assert_eq!(g.e(), 8);
+
+    let neighbors = |name: &str| -> Vec<&str> {
+        let v = *symbol_graph.index(name).unwrap();
+        let mut names: Vec<&str> = g.adj(v).iter().map(|&w| symbol_graph.name(w).unwrap()).collect();
+        names.sort();
+        names
+    };
+
+    assert_eq!(neighbors("Apollo 13 (1995)"), ["Bacon, Kevin", "Hanks, Tom", "Paxton, Bill"]);
+    assert_eq!(neighbors("Bacon, Kevin"), ["Animal House (1978)", "Apollo 13 (1995)"]);
+    assert_eq!(neighbors("Hanks, Tom"), ["Apollo 13 (1995)", "Big (1988)"]);
+    assert_eq!(neighbors("Perkins, Elizabeth"), ["Big (1988)"]);
+
+    // 演员之间没有直接相连
+    let bacon = *symbol_graph.index("Bacon, Kevin").unwrap();
+    let hanks = *symbol_graph.index("Hanks, Tom").unwrap();
+    assert!(! g.contains_edge(bacon, hanks));
+}
@@ -46,6 +46,88 @@ impl TwoColor {
     pub fn is_two_colorable(&self) -> bool {
         self.is_two_colorable
     }
+
+    // 顶点的颜色，同一条边的两个顶点颜色不同
+    pub fn color(&self, v: usize) -> bool {
+        self.color[v]
+    }
+}
+
+// 动态二分图检测，逐条加边，使用带奇偶性的并查集
+// 每个顶点记录与父节点的颜色是否不同，沿路径异或得到与根节点的颜色关系
+pub struct DynamicTwoColor {
+    id: Vec<usize>,
+    sz: Vec<usize>,
+    parity: Vec<bool>,  // 与父节点颜色不同
+    e: usize,
+}
+
+impl DynamicTwoColor {
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut this = DynamicTwoColor {
+            id: Vec::with_capacity(capacity),
+            sz: Vec::with_capacity(capacity),
+            parity: Vec::with_capacity(capacity),
+            e: 0,
+        };
+
+        for i in 0..capacity {
+            this.id.push(i);
+            this.sz.push(1);
+            this.parity.push(false);
+        }
+
+        this
+    }
+
+    // 已接受的边数
+    pub fn e(&self) -> usize {
+        self.e
+    }
+
+    // 根节点和与根节点的颜色是否不同
+    fn find(&self, mut p: usize) -> (usize, bool) {
+        let mut parity = false;
+
+        while p != self.id[p] {
+            parity ^= self.parity[p];
+            p = self.id[p];
+        }
+
+        (p, parity)
+    }
+
+    // 加边后仍为二分图时接受并返回 true，会形成奇数长度的环（包括自环）时拒绝并返回 false
+    pub fn add_edge(&mut self, v: usize, w: usize) -> bool {
+        let (i, pv) = self.find(v);
+        let (j, pw) = self.find(w);
+
+        if i == j {
+            if pv == pw {
+                return false
+            }
+        }
+        else {
+            // 合并后 v 和 w 的颜色不同
+            let (small, large) = if self.sz[i] < self.sz[j] { (i, j) } else { (j, i) };
+            self.id[small] = large;
+            self.sz[large] += self.sz[small];
+            self.parity[small] = ! (pv ^ pw);
+        }
+
+        self.e += 1;
+        true
+    }
+
+    // 被拒绝的边不会加入，所以当前的图总是二分图
+    pub fn is_bipartite(&self) -> bool {
+        true
+    }
+
+    // 顶点的颜色，已接受的每条边的两个顶点颜色不同
+    pub fn color(&self, v: usize) -> bool {
+        self.find(v).1
+    }
 }
 
 
@@ -81,3 +163,39 @@ fn test() {
     let two_color = TwoColor::new(&g);
     assert!(two_color.is_two_colorable());
 }
+
+#[test]
+fn test_dynamic() {
+    let mut two_color = DynamicTwoColor::with_capacity(6);
+
+    // 偶数长度的环 0-1-2-3-4-5-0
+    for v in 0..6 {
+        assert!(two_color.add_edge(v, (v + 1) % 6));
+    }
+
+    assert!(two_color.is_bipartite());
+    assert_eq!(two_color.e(), 6);
+
+    // 0-2 会形成奇数长度的环 0-1-2-0，被拒绝
+    assert!(! two_color.add_edge(0, 2));
+    assert!(! two_color.add_edge(3, 3));
+    assert_eq!(two_color.e(), 6);
+
+    // 0-3 形成偶数长度的环，被接受
+    assert!(two_color.add_edge(0, 3));
+    assert!(two_color.is_bipartite());
+
+    for v in 0..6 {
+        assert_ne!(two_color.color(v), two_color.color((v + 1) % 6));
+    }
+
+    // 与 TwoColor 的结果一致
+    let mut g = Graph::with_capacity(6);
+
+    for v in 0..6 {
+        g.add_edge(v, (v + 1) % 6);
+    }
+
+    g.add_edge(0, 3);
+    assert!(TwoColor::new(&g).is_two_colorable());
+}
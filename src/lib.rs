@@ -1,4 +1,7 @@
 // 算法（第四版）练习
+#[cfg(test)]
+extern crate rand;
+
 pub mod sort;
 pub mod queue;
 pub mod tree;
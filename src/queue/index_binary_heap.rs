@@ -1,3 +1,5 @@
+use std::fmt;
+
 // 索引优先队列
 pub struct IndexBinaryHeap<T> {
     n: usize,                   // 元素数量
@@ -6,6 +8,21 @@ pub struct IndexBinaryHeap<T> {
     keys: Vec<Option<T>>,       // keys[n], 数字 n 关联的对象
 }
 
+#[derive(Debug, PartialEq)]
+pub enum HeapError {
+    IndexOutOfRange { index: usize, capacity: usize },
+}
+
+impl fmt::Display for HeapError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            HeapError::IndexOutOfRange { index, capacity } => {
+                write!(f, "index {} out of range, capacity is {}", index, capacity)
+            },
+        }
+    }
+}
+
 impl<T: PartialOrd> IndexBinaryHeap<T> {
     pub fn with_capacity(capacity: usize) -> Self {
         let mut this = IndexBinaryHeap {
@@ -24,7 +41,36 @@ impl<T: PartialOrd> IndexBinaryHeap<T> {
         this
     }
 
+    // 索引超过容量时自动扩容，容量至少翻倍
     pub fn put(&mut self, i: usize, key: T) {
+        if i >= self.capacity() {
+            self.grow(i + 1);
+        }
+
+        if let Err(err) = self.try_put(i, key) {
+            panic!("{}", err);
+        }
+    }
+
+    // 索引不存在时才添加，已存在时保留原对象，返回是否添加
+    pub fn put_if_absent(&mut self, i: usize, key: T) -> bool {
+        if self.contains(i) {
+            return false
+        }
+
+        self.put(i, key);
+
+        true
+    }
+
+    // 索引必须小于容量，越界时返回错误，不会扩容
+    pub fn try_put(&mut self, i: usize, key: T) -> Result<(), HeapError> {
+        let capacity = self.capacity();
+
+        if i >= capacity {
+            return Err(HeapError::IndexOutOfRange { index: i, capacity })
+        }
+
         if ! self.contains(i) {
             // 添加元素, 放到最后，然后上浮
             let k = self.n + 1;
@@ -43,9 +89,16 @@ impl<T: PartialOrd> IndexBinaryHeap<T> {
             self.swim(k);
             self.sink(k);
         }
+
+        Ok(())
     }
 
     pub fn pop(&mut self) -> usize {
+        self.pop_entry().0
+    }
+
+    // 删除堆顶，返回索引和关联的对象
+    fn pop_entry(&mut self) -> (usize, T) {
         // 堆顶
         let v = self.pq[1].unwrap();
 
@@ -60,23 +113,83 @@ impl<T: PartialOrd> IndexBinaryHeap<T> {
         // 删掉值
         self.pq[k] = None;
         self.qp[v] = None;
-        self.keys[v] = None;
 
-        v
+        (v, self.keys[v].take().unwrap())
+    }
+
+    // 取出所有元素，按对象由小到大排列
+    pub fn into_sorted_vec(mut self) -> Vec<(usize, T)> {
+        let mut res = Vec::with_capacity(self.size());
+
+        // 堆顶是最大的对象，倒序后即为升序
+        while ! self.is_empty() {
+            res.push(self.pop_entry());
+        }
+
+        res.reverse();
+        res
+    }
+
+    // 清空队列，保留已分配的容量以便重复使用
+    pub fn clear(&mut self) {
+        for k in 1..self.n + 1 {
+            if let Some(i) = self.pq[k].take() {
+                self.qp[i] = None;
+                self.keys[i] = None;
+            }
+        }
+
+        self.n = 0;
     }
 
     pub fn is_empty(&self) -> bool {
         self.size() == 0
     }
 
+    // 超过容量的索引不存在
     pub fn contains(&self, i: usize) -> bool {
-        self.qp[i].is_some()
+        self.qp.get(i).map_or(false, |slot| slot.is_some())
+    }
+
+    // 索引的上限，put 时会自动扩容
+    pub fn capacity(&self) -> usize {
+        self.qp.len() - 1
+    }
+
+    // 扩容到至少 min_capacity，已有元素的位置不变，堆的性质不受影响
+    fn grow(&mut self, min_capacity: usize) {
+        let capacity = min_capacity.max(2 * self.capacity());
+
+        while self.qp.len() < capacity + 1 {
+            self.pq.push(None);
+            self.qp.push(None);
+            self.keys.push(None);
+        }
     }
 
     pub fn size(&self) -> usize {
         self.n
     }
 
+    // 索引关联的对象
+    pub fn key_of(&self, i: usize) -> Option<&T> {
+        match self.keys.get(i) {
+            Some(&Some(ref key)) => Some(key),
+            _ => None,
+        }
+    }
+
+    // 堆顶的索引，即 pop 将返回的索引，不删除元素
+    // 堆顶是对象最大的元素，需要最小值时用 Reverse 包装对象
+    pub fn top_index(&self) -> Option<usize> {
+        if self.is_empty() {
+            None
+        }
+        else {
+            self.pq[1]
+        }
+    }
+
     fn compare(&self, i: usize, j: usize) -> bool {
         if let (Some(n), Some(m)) = (self.pq[i], self.pq[j]) {
             match (&self.keys[n], &self.keys[m]) {
@@ -193,4 +306,147 @@ fn test() {
     assert_eq!(pq.pop(), 8);
     assert_eq!(pq.pop(), 0);
     assert_eq!(pq.pop(), 1);
+}
+
+#[test]
+fn test_key_of() {
+    let mut pq = IndexBinaryHeap::with_capacity(10);
+    assert_eq!(pq.top_index(), None);
+
+    pq.put(3, 0.5);
+    pq.put(7, 0.2);
+    pq.put(1, 0.9);
+
+    assert_eq!(pq.key_of(3), Some(&0.5));
+    assert_eq!(pq.key_of(7), Some(&0.2));
+    assert_eq!(pq.key_of(1), Some(&0.9));
+    assert_eq!(pq.key_of(2), None);
+    assert_eq!(pq.key_of(100), None);
+    // 0.9 最大
+    assert_eq!(pq.top_index(), Some(1));
+
+    pq.put(7, 1.5);
+    assert_eq!(pq.key_of(7), Some(&1.5));
+    assert_eq!(pq.top_index(), Some(7));
+
+    // 使用 Reverse 时堆顶是最小的对象
+    use std::cmp::Reverse;
+
+    let mut min_pq = IndexBinaryHeap::with_capacity(10);
+    min_pq.put(3, Reverse(5));
+    min_pq.put(7, Reverse(2));
+    min_pq.put(1, Reverse(9));
+    assert_eq!(min_pq.top_index(), Some(7));
+    assert_eq!(min_pq.pop(), 7);
+
+    assert_eq!(pq.pop(), 7);
+    assert_eq!(pq.key_of(7), None);
+}
+
+#[test]
+fn test_try_put() {
+    let mut pq = IndexBinaryHeap::with_capacity(4);
+
+    assert_eq!(pq.try_put(4, 0.1), Err(HeapError::IndexOutOfRange { index: 4, capacity: 4 }));
+    assert_eq!(pq.try_put(10, 0.1), Err(HeapError::IndexOutOfRange { index: 10, capacity: 4 }));
+    assert!(pq.is_empty());
+
+    assert_eq!(pq.try_put(0, 0.1), Ok(()));
+    assert_eq!(pq.try_put(3, 0.3), Ok(()));
+    assert_eq!(pq.size(), 2);
+    assert_eq!(pq.pop(), 3);
+}
+
+#[test]
+fn test_grow() {
+    let mut pq = IndexBinaryHeap::with_capacity(4);
+    assert_eq!(pq.capacity(), 4);
+    assert!(! pq.contains(10));
+
+    pq.put(1, 0.3);
+    pq.put(3, 0.9);
+
+    // 翻倍
+    pq.put(4, 0.5);
+    assert_eq!(pq.capacity(), 8);
+
+    // 翻倍不够时扩容到索引所需的大小
+    pq.put(100, 0.7);
+    assert_eq!(pq.capacity(), 101);
+    assert!(pq.contains(100));
+    assert_eq!(pq.key_of(100), Some(&0.7));
+    assert_eq!(pq.size(), 4);
+
+    assert_eq!(pq.pop(), 3);
+    assert_eq!(pq.pop(), 100);
+    assert_eq!(pq.pop(), 4);
+    assert_eq!(pq.pop(), 1);
+    assert!(pq.is_empty());
+
+    // try_put 不会扩容
+    assert_eq!(pq.try_put(101, 0.1), Err(HeapError::IndexOutOfRange { index: 101, capacity: 101 }));
+
+    let mut pq = IndexBinaryHeap::with_capacity(0);
+    pq.put(0, 1);
+    assert_eq!(pq.pop(), 0);
+}
+
+#[test]
+fn test_clear() {
+    let mut pq = IndexBinaryHeap::with_capacity(10);
+
+    pq.put(0, 0.33);
+    pq.put(5, 0.001);
+    pq.put(8, 0.01);
+
+    pq.clear();
+
+    assert!(pq.is_empty());
+    assert!(! pq.contains(0));
+    assert!(! pq.contains(5));
+    assert_eq!(pq.key_of(8), None);
+
+    pq.put(5, 0.2);
+    pq.put(9, 0.7);
+    pq.put(0, 0.1);
+
+    assert_eq!(pq.size(), 3);
+    assert_eq!(pq.pop(), 9);
+    assert_eq!(pq.pop(), 5);
+    assert_eq!(pq.pop(), 0);
+    assert!(pq.is_empty());
+}
+
+#[test]
+fn test_into_sorted_vec() {
+    let mut pq = IndexBinaryHeap::with_capacity(8);
+
+    for &(i, key) in [(3, 40), (0, 70), (6, 10), (1, 30), (7, 80), (2, 20), (5, 60), (4, 50)].iter() {
+        pq.put(i, key);
+    }
+
+    let sorted = pq.into_sorted_vec();
+
+    assert_eq!(sorted, [(6, 10), (2, 20), (1, 30), (3, 40), (4, 50), (5, 60), (0, 70), (7, 80)]);
+    assert!(sorted.windows(2).all(|w| w[0].1 <= w[1].1));
+
+    let empty: IndexBinaryHeap<f32> = IndexBinaryHeap::with_capacity(4);
+    assert!(empty.into_sorted_vec().is_empty());
+}
+
+#[test]
+fn test_put_if_absent() {
+    let mut pq = IndexBinaryHeap::with_capacity(4);
+
+    assert!(pq.put_if_absent(2, 0.5));
+    assert!(pq.put_if_absent(0, 0.1));
+    assert!(! pq.put_if_absent(2, 0.9));
+
+    assert_eq!(pq.key_of(2), Some(&0.5));
+    assert_eq!(pq.size(), 2);
+    assert_eq!(pq.pop(), 2);
+
+    // 删除后可以再次添加
+    assert!(pq.put_if_absent(2, 0.05));
+    assert_eq!(pq.pop(), 0);
 }
\ No newline at end of file
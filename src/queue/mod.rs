@@ -1 +1,2 @@
-pub mod index_binary_heap;
\ No newline at end of file
+pub mod index_binary_heap;
+pub mod ordered_float;
\ No newline at end of file
@@ -0,0 +1,57 @@
+use std::cmp::Ordering;
+
+// 可以全排序的 f32，用作优先队列中的权重
+// 按 IEEE 754 的 totalOrder 比较位模式：
+//   -NaN < -inf < ... < -0.0 < +0.0 < ... < +inf < +NaN
+// 所以正的 NaN 比所有数字都大，-0.0 和 +0.0 不相等
+// 需要由小到大出队时，配合 std::cmp::Reverse 使用
+#[derive(Debug, Clone, Copy)]
+pub struct OrderedFloat(pub f32);
+
+impl Ord for OrderedFloat {
+    fn cmp(&self, other: &OrderedFloat) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl PartialOrd for OrderedFloat {
+    fn partial_cmp(&self, other: &OrderedFloat) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for OrderedFloat {
+    fn eq(&self, other: &OrderedFloat) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for OrderedFloat {}
+
+
+#[test]
+fn test() {
+    use std::f32;
+
+    let mut seq = [
+        OrderedFloat(3.0),
+        OrderedFloat(f32::NAN),
+        OrderedFloat(-1.0),
+        OrderedFloat(f32::INFINITY),
+        OrderedFloat(0.5),
+        OrderedFloat(-0.0),
+        OrderedFloat(0.0),
+    ];
+
+    seq.sort();
+
+    let bits: Vec<u32> = seq.iter().map(|n| n.0.to_bits()).collect();
+    let expected: Vec<u32> = [-1.0, -0.0, 0.0, 0.5, 3.0, f32::INFINITY, f32::NAN]
+        .iter()
+        .map(|n: &f32| n.to_bits())
+        .collect();
+
+    assert_eq!(bits, expected);
+    assert_eq!(OrderedFloat(f32::NAN), OrderedFloat(f32::NAN));
+    assert!(OrderedFloat(-0.0) < OrderedFloat(0.0));
+}
@@ -1 +1,2 @@
-pub mod trie_st;
\ No newline at end of file
+pub mod trie_st;
+pub mod tst;
\ No newline at end of file
@@ -1,5 +1,6 @@
 // 字典树
 use std::fmt;
+use std::iter::FromIterator;
 const R: usize = 256;
 
 type Link<T> = Option<Box<Node<T>>>;
@@ -7,78 +8,252 @@ type Link<T> = Option<Box<Node<T>>>;
 #[derive(Debug)]
 struct Node<T> {
     val: Option<T>,
-    next: Vec<Link<T>>
+    next: Vec<(usize, Link<T>)>     // 只保存存在的子节点，按字符索引有序
+}
+
+impl<T> Node<T> {
+    // 二分查找子节点的位置，不存在时返回应插入的位置
+    fn position(&self, c: usize) -> Result<usize, usize> {
+        self.next.binary_search_by_key(&c, |&(k, _)| k)
+    }
+
+    fn child(&self, c: usize) -> Option<&Link<T>> {
+        self.position(c).ok().map(|i| &self.next[i].1)
+    }
+
+    fn child_mut(&mut self, c: usize) -> Option<&mut Link<T>> {
+        match self.position(c) {
+            Ok(i) => Some(&mut self.next[i].1),
+            Err(_) => None,
+        }
+    }
+
+    // 子节点不存在时插入空链接，保持有序
+    fn child_or_insert(&mut self, c: usize) -> &mut Link<T> {
+        let i = match self.position(c) {
+            Ok(i) => i,
+            Err(i) => {
+                self.next.insert(i, (c, None));
+                i
+            },
+        };
+
+        &mut self.next[i].1
+    }
 }
 
 trait LinkMethods<T> {
     fn new() -> Link<T>;
-    fn get(&self, key: &str, d: usize) -> &Link<T>;
-    fn put(&mut self, key: &str, val: T, d: usize) -> Link<T>;
+    fn get(&self, key: &[usize]) -> &Link<T>;
+    fn get_mut(&mut self, key: &[usize]) -> Option<&mut T>;
+    fn put(&mut self, key: &[usize], val: T);
+    fn entry(&mut self, key: &[usize]) -> &mut Option<T>;
+    fn count(&self) -> usize;
+    fn remove_prefix(&mut self, key: &[usize], d: usize) -> usize;
+    fn retain<F: FnMut(&str, &T) -> bool>(&mut self, pre: &mut String, to_char: fn(usize) -> char, f: &mut F);
+    fn is_prunable(&self) -> bool;
 }
 
 impl<T: fmt::Debug> LinkMethods<T> for Link<T> {
     fn new() -> Self {
-        let mut this = Box::new(Node {
+        Some(Box::new(Node {
             val: None,
-            next: Vec::with_capacity(R),
-        });
+            next: Vec::new(),
+        }))
+    }
+
+    // 逐个字符向下查找，不使用递归，避免长键导致栈溢出
+    fn get(&self, key: &[usize]) -> &Self {
+        let mut x = self;
+
+        for &c in key {
+            x = match *x {
+                Some(ref boxed_node) => match boxed_node.child(c) {
+                    Some(link) => link,
+                    None => return &None,
+                },
+                None => break,
+            };
+        }
+
+        x
+    }
+
+    fn get_mut(&mut self, key: &[usize]) -> Option<&mut T> {
+        let mut x = self;
+
+        for &c in key {
+            x = match *x {
+                Some(ref mut boxed_node) => boxed_node.child_mut(c)?,
+                None => return None,
+            };
+        }
+
+        x.as_mut().and_then(|boxed_node| boxed_node.val.as_mut())
+    }
+
+    fn put(&mut self, key: &[usize], val: T) {
+        *self.entry(key) = Some(val);
+    }
+
+    // 键对应的值的位置，逐个字符向下查找，缺失的节点直接创建
+    fn entry(&mut self, key: &[usize]) -> &mut Option<T> {
+        let mut x = self;
 
-        for _ in 0..R {
-            this.next.push(None);
+        for &c in key {
+            if x.is_none() {
+                *x = Self::new();
+            }
+
+            x = x.as_mut().unwrap().child_or_insert(c);
         }
 
-        Some(this)
+        if x.is_none() {
+            *x = Self::new();
+        }
+
+        &mut x.as_mut().unwrap().val
     }
 
-    fn get(&self, key: &str, d: usize) -> &Self {
+    // 以当前节点为根的子树中键的数量
+    fn count(&self) -> usize {
         match *self {
-            None => &self,
-            Some(_) if d == key.chars().count() => &self,
             Some(ref boxed_node) => {
-                let c = key.chars().nth(d).unwrap() as usize;
-                boxed_node.next[c].get(key, d + 1)
-            }
+                let mut count = if boxed_node.val.is_some() { 1 } else { 0 };
+
+                for &(_, ref link) in &boxed_node.next {
+                    count += link.count();
+                }
+
+                count
+            },
+            None => 0,
         }
     }
 
-    fn put(&mut self, key: &str, val: T, d: usize) -> Link<T> {
-        let mut x = match self.take() {
-            Some(mut boxed_node) => boxed_node,
-            None => Self::new().unwrap(),
+    // 删除前缀节点的整棵子树，并删除沿途变空的节点，返回删除的键的数量
+    fn remove_prefix(&mut self, key: &[usize], d: usize) -> usize {
+        if self.is_none() {
+            return 0
+        }
+
+        if d == key.len() {
+            let count = self.count();
+            *self = None;
+            return count
+        }
+
+        let removed = {
+            let boxed_node = self.as_mut().unwrap();
+
+            match boxed_node.position(key[d]) {
+                Ok(i) => {
+                    let removed = boxed_node.next[i].1.remove_prefix(key, d + 1);
+
+                    if boxed_node.next[i].1.is_none() {
+                        boxed_node.next.remove(i);
+                    }
+
+                    removed
+                },
+                Err(_) => 0,
+            }
         };
 
-        if d == key.chars().count() {
-            x.val = Some(val);
+        if removed > 0 && self.is_prunable() {
+            *self = None;
         }
-        else {
-            let c = key.chars().nth(d).unwrap() as usize;
-            x.next[c] = x.next[c].put(key, val, d + 1);
+
+        removed
+    }
+
+    // 删除 f 返回 false 的键，pre 是当前节点对应的键
+    fn retain<F: FnMut(&str, &T) -> bool>(&mut self, pre: &mut String, to_char: fn(usize) -> char, f: &mut F) {
+        if let Some(ref mut boxed_node) = *self {
+            let keep = match boxed_node.val {
+                Some(ref val) => f(pre, val),
+                None => true,
+            };
+
+            if ! keep {
+                boxed_node.val = None;
+            }
+
+            for &mut (c, ref mut link) in boxed_node.next.iter_mut() {
+                pre.push(to_char(c));
+                link.retain(pre, to_char, f);
+                pre.pop();
+            }
+
+            boxed_node.next.retain(|&(_, ref link)| link.is_some());
         }
 
-        Some(x)
+        if self.is_prunable() {
+            *self = None;
+        }
+    }
+
+    // 节点没有值也没有子节点
+    fn is_prunable(&self) -> bool {
+        match *self {
+            Some(ref boxed_node) => {
+                boxed_node.val.is_none() && boxed_node.next.is_empty()
+            },
+            None => false,
+        }
     }
 }
 
 #[derive(Debug)]
 pub struct TrieST<T> {
     root: Link<T>,
+    r: usize,                       // 字母表大小，每个节点最多的子节点数量
+    to_index: fn(char) -> usize,    // 字符 -> 索引
+    to_char: fn(usize) -> char,     // 索引 -> 字符
 }
 
 impl<T: fmt::Debug> TrieST<T> {
 
     pub fn new() -> Self {
-        TrieST { root: None }
+        TrieST::with_radix(R, |c| c as usize, |i| i as u8 as char)
+    }
+
+    // 自定义字母表，例如 DNA 只需要 ACGT 4 个字符，to_index 的返回值必须小于 r
+    pub fn with_radix(r: usize, to_index: fn(char) -> usize, to_char: fn(usize) -> char) -> Self {
+        TrieST { root: None, r, to_index, to_char }
+    }
+
+    // 由键值对构建，与 to_vec 配合使用
+    pub fn from_pairs<S: AsRef<str>, I: IntoIterator<Item = (S, T)>>(pairs: I) -> Self {
+        let mut trie_st = TrieST::new();
+
+        for (key, val) in pairs {
+            trie_st.put(key.as_ref(), val);
+        }
+
+        trie_st
     }
 
     pub fn get(&self, key: &str) -> &Option<T> {
-        match *self.root.get(key, 0) {
+        match *self.root.get(&self.indices(key)) {
             Some(ref boxed_node) => &boxed_node.val,
             None => &None,
         }
     }
 
+    pub fn contains(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    // 获取值的可变引用，可以直接修改已存在的值
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut T> {
+        let key = self.indices(key);
+        self.root.get_mut(&key)
+    }
+
     pub fn put(&mut self, key: &str, val: T) {
-        self.root = self.root.put(key, val, 0);
+        let key = self.indices(key);
+        self.root.put(&key, val);
     }
 
     // 查找所有键
@@ -90,28 +265,380 @@ impl<T: fmt::Debug> TrieST<T> {
     pub fn keys_with_prefix(&self, pre: &str) -> Vec<String> {
         let pre = String::from(pre);
         let mut q = Vec::new();
-        self.collect(self.root.get(pre.as_ref(), 0), pre, &mut q);
+        self.collect(self.root.get(&self.indices(&pre)), pre, &mut q);
+        q.into_iter().map(|(key, _)| key).collect()
+    }
+
+    // 按字典序导出所有键值对
+    pub fn to_vec(&self) -> Vec<(String, &T)> {
+        let mut q = Vec::new();
+        self.collect(&self.root, String::new(), &mut q);
         q
     }
 
-    fn collect(&self, node: &Link<T>, pre: String, q: &mut Vec<String>) {
+    // 按字典序逐个返回键值对，不预先收集全部结果
+    pub fn iter(&self) -> impl Iterator<Item = (String, &T)> {
+        Iter {
+            stack: self.root.iter().map(|boxed_node| (&**boxed_node, String::new(), 0)).collect(),
+            to_char: self.to_char,
+        }
+    }
+
+    // 统计前缀匹配的键的数量，不生成键
+    pub fn count_prefix(&self, pre: &str) -> usize {
+        self.root.get(&self.indices(pre)).count()
+    }
+
+    // 删除所有以 pre 为前缀的键（包括 pre 本身），返回删除的键的数量
+    pub fn remove_prefix(&mut self, pre: &str) -> usize {
+        let key = self.indices(pre);
+        self.root.remove_prefix(&key, 0)
+    }
+
+    // 只保留 f 返回 true 的键，并删除变空的节点
+    pub fn retain<F: FnMut(&str, &T) -> bool>(&mut self, mut f: F) {
+        self.root.retain(&mut String::new(), self.to_char, &mut f);
+    }
+
+    // 所有键的最长公共前缀：从根节点出发，沿唯一的子节点向下，直到出现分叉或遇到值
+    pub fn longest_common_prefix(&self) -> String {
+        let mut pre = String::new();
+        let mut x = &self.root;
+
+        while let Some(ref boxed_node) = *x {
+            if boxed_node.val.is_some() {
+                break
+            }
+
+            if boxed_node.next.len() != 1 {
+                break
+            }
+
+            let (c, ref link) = boxed_node.next[0];
+            pre.push((self.to_char)(c));
+            x = link;
+        }
+
+        pre
+    }
+
+    // 小于等于 key 的最大键，按字典序
+    pub fn floor(&self, key: &str) -> Option<String> {
+        let key = self.indices(key);
+        let mut res = None;
+        let mut x = &self.root;
+
+        // 越深的候选键与 key 的公共前缀越长，所以越接近 key
+        for d in 0..key.len() + 1 {
+            let boxed_node = match *x {
+                Some(ref boxed_node) => boxed_node,
+                None => break,
+            };
+
+            if d == key.len() {
+                if boxed_node.val.is_some() {
+                    res = Some(self.to_key(&key));
+                }
+
+                break
+            }
+
+            // 比 key[d] 小的最大子节点中的最大键，否则是当前前缀本身
+            let i = match boxed_node.position(key[d]) {
+                Ok(i) | Err(i) => i,
+            };
+
+            if i > 0 {
+                let (c, ref link) = boxed_node.next[i - 1];
+                let mut pre = key[..d].to_vec();
+                pre.push(c);
+                res = Some(self.max_key(link, pre));
+            }
+            else if boxed_node.val.is_some() {
+                res = Some(self.to_key(&key[..d]));
+            }
+
+            x = match boxed_node.child(key[d]) {
+                Some(link) => link,
+                None => break,
+            };
+        }
+
+        res
+    }
+
+    // 大于等于 key 的最小键，按字典序
+    pub fn ceiling(&self, key: &str) -> Option<String> {
+        let key = self.indices(key);
+        let mut res = None;
+        let mut x = &self.root;
+
+        for d in 0..key.len() + 1 {
+            let boxed_node = match *x {
+                Some(ref boxed_node) => boxed_node,
+                None => break,
+            };
+
+            // 以 key 为前缀的键都大于等于 key
+            if d == key.len() {
+                res = Some(self.min_key(x, key.clone()));
+                break
+            }
+
+            // 比 key[d] 大的最小子节点中的最小键
+            let i = match boxed_node.position(key[d]) {
+                Ok(i) => i + 1,
+                Err(i) => i,
+            };
+
+            if let Some(&(c, ref link)) = boxed_node.next.get(i) {
+                let mut pre = key[..d].to_vec();
+                pre.push(c);
+                res = Some(self.min_key(link, pre));
+            }
+
+            x = match boxed_node.child(key[d]) {
+                Some(link) => link,
+                None => break,
+            };
+        }
+
+        res
+    }
+
+    // 按字典序小于 key 的键的数量，沿 key 向下，累加路径左侧子树中的键和路径上的前缀键
+    // 子树中键的数量需要遍历子树，最坏情况为线性时间
+    pub fn rank(&self, key: &str) -> usize {
+        let key = self.indices(key);
+        let mut rank = 0;
+        let mut x = &self.root;
+
+        for &c in &key {
+            let boxed_node = match *x {
+                Some(ref boxed_node) => boxed_node,
+                None => break,
+            };
+
+            // 当前节点的键是 key 的真前缀，比 key 小
+            if boxed_node.val.is_some() {
+                rank += 1;
+            }
+
+            for &(_, ref link) in boxed_node.next.iter().take_while(|&&(k, _)| k < c) {
+                rank += link.count();
+            }
+
+            x = match boxed_node.child(c) {
+                Some(link) => link,
+                None => break,
+            };
+        }
+
+        rank
+    }
+
+    // 按字典序排名为 k 的键（从 0 开始），k 不小于键的数量时返回 None
+    pub fn select(&self, k: usize) -> Option<String> {
+        let mut k = k;
+        let mut pre = Vec::new();
+        let mut x = &self.root;
+
+        'descend: while let Some(ref boxed_node) = *x {
+            if boxed_node.val.is_some() {
+                if k == 0 {
+                    return Some(self.to_key(&pre))
+                }
+
+                k -= 1;
+            }
+
+            for &(c, ref link) in &boxed_node.next {
+                let count = link.count();
+
+                if k < count {
+                    pre.push(c);
+                    x = link;
+                    continue 'descend
+                }
+
+                k -= count;
+            }
+
+            break
+        }
+
+        None
+    }
+
+    // 子树中最小的键：沿最小的子节点向下，直到遇到值
+    fn min_key(&self, node: &Link<T>, mut pre: Vec<usize>) -> String {
+        let mut x = node;
+
+        while let Some(ref boxed_node) = *x {
+            if boxed_node.val.is_some() {
+                break
+            }
+
+            match boxed_node.next.first() {
+                Some(&(c, ref link)) => {
+                    pre.push(c);
+                    x = link;
+                },
+                None => break,
+            }
+        }
+
+        self.to_key(&pre)
+    }
+
+    // 子树中最大的键：沿最大的子节点向下，直到叶子节点
+    fn max_key(&self, node: &Link<T>, mut pre: Vec<usize>) -> String {
+        let mut x = node;
+
+        while let Some(ref boxed_node) = *x {
+            match boxed_node.next.last() {
+                Some(&(c, ref link)) => {
+                    pre.push(c);
+                    x = link;
+                },
+                None => break,
+            }
+        }
+
+        self.to_key(&pre)
+    }
+
+    // 将键转换为字母表中的索引
+    fn indices(&self, key: &str) -> Vec<usize> {
+        let r = self.r;
+
+        key.chars()
+            .map(self.to_index)
+            .inspect(|&c| assert!(c < r, "index {} out of radix {}", c, r))
+            .collect()
+    }
+
+    // 将字母表中的索引转换为键
+    fn to_key(&self, indices: &[usize]) -> String {
+        indices.iter().map(|&c| (self.to_char)(c)).collect()
+    }
+
+    fn collect<'a>(&self, node: &'a Link<T>, pre: String, q: &mut Vec<(String, &'a T)>) {
         let boxed_node = match *node {
             Some(ref boxed_node) => boxed_node,
             None => return,
         };
 
-        if boxed_node.val.is_some() {
-            q.push(pre.clone());
+        if let Some(ref val) = boxed_node.val {
+            q.push((pre.clone(), val));
         }
 
-        for c in 0..R {
-            match String::from_utf8(vec![c as u8]) {
-                Ok(cc) => {
-                    let mut pre = pre.clone();
-                    pre.push_str(cc.as_ref());
-                    self.collect(&boxed_node.next[c], pre, q);
-                },
-                Err(_) => {},
+        for &(c, ref link) in &boxed_node.next {
+            let mut pre = pre.clone();
+            pre.push((self.to_char)(c));
+            self.collect(link, pre, q);
+        }
+    }
+
+    // 每个节点的子节点字符和链接是否非空，只在测试中使用
+    #[cfg(test)]
+    fn children(&self) -> Vec<Vec<(usize, bool)>> {
+        let mut res = Vec::new();
+        let mut stack: Vec<&Node<T>> = self.root.iter().map(|boxed_node| &**boxed_node).collect();
+
+        while let Some(node) = stack.pop() {
+            res.push(node.next.iter().map(|&(c, ref link)| (c, link.is_some())).collect());
+            stack.extend(node.next.iter().filter_map(|&(_, ref link)| link.as_ref().map(|boxed_node| &**boxed_node)));
+        }
+
+        res
+    }
+}
+
+impl<T: fmt::Debug> Default for TrieST<T> {
+    fn default() -> Self {
+        TrieST::new()
+    }
+}
+
+// 值为 () 的字典树作为字符串集合使用，重复的键只保留一个
+impl<'a> FromIterator<&'a str> for TrieST<()> {
+    fn from_iter<I: IntoIterator<Item = &'a str>>(iter: I) -> Self {
+        let mut trie_st = TrieST::new();
+
+        for key in iter {
+            trie_st.put(key, ());
+        }
+
+        trie_st
+    }
+}
+
+impl FromIterator<String> for TrieST<()> {
+    fn from_iter<I: IntoIterator<Item = String>>(iter: I) -> Self {
+        let mut trie_st = TrieST::new();
+
+        for key in iter {
+            trie_st.put(&key, ());
+        }
+
+        trie_st
+    }
+}
+
+impl TrieST<usize> {
+    // 词频统计：键不存在时计为 1，存在时加 1，只向下查找一次
+    pub fn increment(&mut self, key: &str) {
+        let key = self.indices(key);
+        let val = self.root.entry(&key);
+
+        *val = Some(val.map_or(1, |count| count + 1));
+    }
+}
+
+// 深度优先遍历的栈，保存节点、节点对应的键和下一步的位置
+// 位置为 0 表示还未返回节点自身的值，为 i 表示下一个访问第 i - 1 个子节点
+struct Iter<'a, T: 'a> {
+    stack: Vec<(&'a Node<T>, String, usize)>,
+    to_char: fn(usize) -> char,
+}
+
+impl<'a, T: 'a> Iterator for Iter<'a, T> {
+    type Item = (String, &'a T);
+
+    fn next(&mut self) -> Option<(String, &'a T)> {
+        while let Some((node, pre, i)) = self.stack.pop() {
+            if i == 0 {
+                self.stack.push((node, pre.clone(), 1));
+
+                if let Some(ref val) = node.val {
+                    return Some((pre, val))
+                }
+            }
+            else if let Some(&(c, ref link)) = node.next.get(i - 1) {
+                self.stack.push((node, pre.clone(), i + 1));
+
+                if let Some(ref child) = *link {
+                    let mut pre = pre;
+                    pre.push((self.to_char)(c));
+                    self.stack.push((child, pre, 0));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+// 逐个释放节点，避免长键递归析构导致栈溢出
+impl<T> Drop for TrieST<T> {
+    fn drop(&mut self) {
+        let mut stack = Vec::new();
+        stack.extend(self.root.take());
+
+        while let Some(mut boxed_node) = stack.pop() {
+            for (_, link) in boxed_node.next.drain(..) {
+                stack.extend(link);
             }
         }
     }
@@ -131,4 +658,350 @@ fn test() {
     assert_eq!(trie_st.get("def"), &Some(4));
     assert_eq!(trie_st.keys(), ["abc", "abf", "bde", "cbd", "def"]);
     assert_eq!(trie_st.keys_with_prefix("ab"), ["abc", "abf"]);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_get_mut() {
+    let mut trie_st = TrieST::new();
+
+    trie_st.put("abc", 1);
+    trie_st.put("ab", 2);
+
+    if let Some(val) = trie_st.get_mut("abc") {
+        *val += 10;
+    }
+
+    assert_eq!(trie_st.get("abc"), &Some(11));
+    assert_eq!(trie_st.get("ab"), &Some(2));
+    assert_eq!(trie_st.get_mut("a"), None);
+    assert_eq!(trie_st.get_mut("abcd"), None);
+}
+
+#[test]
+fn test_count_prefix() {
+    let mut trie_st = TrieST::new();
+
+    trie_st.put("she", 1);
+    trie_st.put("shells", 2);
+    trie_st.put("shore", 3);
+    trie_st.put("sea", 4);
+
+    assert_eq!(trie_st.count_prefix("sh"), 3);
+    assert_eq!(trie_st.count_prefix("she"), 2);
+    assert_eq!(trie_st.count_prefix("s"), 4);
+    assert_eq!(trie_st.count_prefix(""), 4);
+    assert_eq!(trie_st.count_prefix("x"), 0);
+}
+
+#[test]
+fn test_longest_common_prefix() {
+    let mut trie_st = TrieST::new();
+    assert_eq!(trie_st.longest_common_prefix(), "");
+
+    trie_st.put("flower", 1);
+    trie_st.put("flow", 2);
+    trie_st.put("flight", 3);
+
+    assert_eq!(trie_st.longest_common_prefix(), "fl");
+
+    trie_st.put("dog", 4);
+    assert_eq!(trie_st.longest_common_prefix(), "");
+
+    let mut trie_st = TrieST::new();
+
+    trie_st.put("abc", 1);
+    trie_st.put("ab", 2);
+
+    assert_eq!(trie_st.longest_common_prefix(), "ab");
+}
+
+#[test]
+fn test_with_radix() {
+    fn to_index(c: char) -> usize {
+        match c {
+            'A' => 0,
+            'C' => 1,
+            'G' => 2,
+            'T' => 3,
+            _ => panic!("invalid DNA base: {}", c),
+        }
+    }
+
+    fn to_char(i: usize) -> char {
+        ['A', 'C', 'G', 'T'][i]
+    }
+
+    let mut trie_st = TrieST::with_radix(4, to_index, to_char);
+
+    trie_st.put("ACGT", 1);
+    trie_st.put("ACCA", 2);
+    trie_st.put("TTAG", 3);
+
+    assert_eq!(trie_st.get("ACGT"), &Some(1));
+    assert_eq!(trie_st.get("ACCA"), &Some(2));
+    assert_eq!(trie_st.get("ACC"), &None);
+    assert_eq!(trie_st.keys(), ["ACCA", "ACGT", "TTAG"]);
+    assert_eq!(trie_st.longest_common_prefix(), "");
+    assert_eq!(trie_st.root.as_ref().unwrap().next.len(), 2);
+}
+
+#[test]
+fn test_long_key() {
+    fn to_index(c: char) -> usize {
+        c as usize - 'a' as usize
+    }
+
+    fn to_char(i: usize) -> char {
+        (b'a' + i as u8) as char
+    }
+
+    let key: String = "abcd".chars().cycle().take(200_000).collect();
+    let mut trie_st = TrieST::with_radix(4, to_index, to_char);
+
+    trie_st.put(&key, 1);
+
+    assert_eq!(trie_st.get(&key), &Some(1));
+    assert_eq!(trie_st.get(&key[..199_999]), &None);
+}
+
+#[test]
+fn test_remove_prefix() {
+    let mut trie_st = TrieST::new();
+
+    trie_st.put("app", 1);
+    trie_st.put("apple", 2);
+    trie_st.put("application", 3);
+    trie_st.put("banana", 4);
+
+    assert_eq!(trie_st.remove_prefix("xyz"), 0);
+    assert_eq!(trie_st.remove_prefix("app"), 3);
+    assert_eq!(trie_st.keys(), ["banana"]);
+    assert_eq!(trie_st.get("apple"), &None);
+    assert_eq!(trie_st.get("banana"), &Some(4));
+
+    // 空的祖先节点已经删除
+    assert!(trie_st.root.as_ref().unwrap().child('a' as usize).is_none());
+
+    assert_eq!(trie_st.remove_prefix(""), 1);
+    assert!(trie_st.root.is_none());
+}
+
+#[test]
+fn test_floor_ceiling() {
+    let mut trie_st = TrieST::new();
+
+    trie_st.put("apple", 1);
+    trie_st.put("apricot", 2);
+    trie_st.put("banana", 3);
+
+    // apricot < april < banana
+    assert_eq!(trie_st.floor("april"), Some(String::from("apricot")));
+    assert_eq!(trie_st.ceiling("april"), Some(String::from("banana")));
+
+    // apple < apq < apricot
+    assert_eq!(trie_st.floor("apq"), Some(String::from("apple")));
+    assert_eq!(trie_st.ceiling("apq"), Some(String::from("apricot")));
+
+    // 键本身存在
+    assert_eq!(trie_st.floor("apple"), Some(String::from("apple")));
+    assert_eq!(trie_st.ceiling("apple"), Some(String::from("apple")));
+
+    // 前缀
+    assert_eq!(trie_st.floor("applesauce"), Some(String::from("apple")));
+    assert_eq!(trie_st.ceiling("ap"), Some(String::from("apple")));
+    assert_eq!(trie_st.floor("ap"), None);
+
+    assert_eq!(trie_st.floor("a"), None);
+    assert_eq!(trie_st.ceiling("c"), None);
+    assert_eq!(trie_st.floor("zzz"), Some(String::from("banana")));
+    assert_eq!(trie_st.ceiling(""), Some(String::from("apple")));
+}
+
+#[test]
+fn test_increment() {
+    let mut trie_st = TrieST::new();
+    let text = "it was the best of times it was the worst of times";
+
+    for word in text.split_whitespace() {
+        trie_st.increment(word);
+    }
+
+    assert_eq!(trie_st.get("it"), &Some(2));
+    assert_eq!(trie_st.get("times"), &Some(2));
+    assert_eq!(trie_st.get("best"), &Some(1));
+    assert_eq!(trie_st.get("worst"), &Some(1));
+    assert_eq!(trie_st.get("was"), &Some(2));
+    assert_eq!(trie_st.get("wa"), &None);
+    assert_eq!(trie_st.keys().len(), 7);
+}
+
+#[test]
+fn test_default() {
+    let mut trie_st: TrieST<usize> = Default::default();
+
+    assert!(trie_st.keys().is_empty());
+    assert_eq!(trie_st.count_prefix(""), 0);
+
+    trie_st.put("abc", 1);
+    assert_eq!(trie_st.get("abc"), &Some(1));
+}
+
+#[test]
+fn test_from_iter() {
+    let words = vec!["she", "sells", "sea", "shells", "by", "the", "sea", "shore"];
+    let set: TrieST<()> = words.into_iter().collect();
+
+    assert!(set.contains("she"));
+    assert!(set.contains("sea"));
+    assert!(! set.contains("sh"));
+    assert!(! set.contains("shell"));
+    assert_eq!(set.keys().len(), 7);
+    assert_eq!(set.keys_with_prefix("sh"), ["she", "shells", "shore"]);
+
+    let set: TrieST<()> = "a b a".split(' ').map(String::from).collect();
+    assert_eq!(set.keys(), ["a", "b"]);
+}
+
+#[test]
+fn test_to_vec() {
+    let mut trie_st = TrieST::new();
+
+    trie_st.put("she", 0);
+    trie_st.put("sells", 1);
+    trie_st.put("sea", 2);
+    trie_st.put("shells", 3);
+    trie_st.put("by", 4);
+    trie_st.put("s", 5);
+
+    let pairs = trie_st.to_vec();
+
+    assert_eq!(pairs, [
+        (String::from("by"), &4),
+        (String::from("s"), &5),
+        (String::from("sea"), &2),
+        (String::from("sells"), &1),
+        (String::from("she"), &0),
+        (String::from("shells"), &3),
+    ]);
+
+    let copy = TrieST::from_pairs(pairs.into_iter().map(|(key, val)| (key, *val)));
+
+    for key in trie_st.keys() {
+        assert_eq!(copy.get(&key), trie_st.get(&key));
+    }
+
+    assert_eq!(copy.keys(), trie_st.keys());
+}
+
+#[test]
+fn test_sparse_children() {
+    use rand::{Rng, SeedableRng, XorShiftRng};
+
+    let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+    let mut trie_st = TrieST::new();
+
+    for i in 0..1000 {
+        let key: String = (0..8).map(|_| rng.gen_range(b'a', b'q') as char).collect();
+        trie_st.put(&key, i);
+    }
+
+    let keys = trie_st.keys();
+    assert!(keys.len() > 990);
+
+    for key in &keys {
+        assert!(trie_st.get(key).is_some());
+    }
+
+    // 只保存实际出现的字符，按字符递增且没有空链接，数量不超过 16 而不是字母表大小 256
+    let children = trie_st.children();
+
+    for next in &children {
+        assert!(next.len() <= 16);
+        assert!(next.windows(2).all(|w| w[0].0 < w[1].0));
+        assert!(next.iter().all(|&(_, present)| present));
+    }
+
+    assert_eq!(children.iter().filter(|next| next.is_empty()).count(), keys.len());
+}
+
+#[test]
+fn test_retain() {
+    let mut trie_st = TrieST::new();
+
+    trie_st.put("she", 0);
+    trie_st.put("sells", 1);
+    trie_st.put("sea", 2);
+    trie_st.put("shells", 3);
+    trie_st.put("by", 4);
+    trie_st.put("the", 5);
+    trie_st.put("shore", 7);
+
+    let mut seen = Vec::new();
+    trie_st.retain(|key, &val| {
+        seen.push(String::from(key));
+        val % 2 == 0
+    });
+
+    assert_eq!(seen, ["by", "sea", "sells", "she", "shells", "shore", "the"]);
+    assert_eq!(trie_st.keys(), ["by", "sea", "she"]);
+    assert_eq!(trie_st.get("sea"), &Some(2));
+    assert_eq!(trie_st.get("shells"), &None);
+
+    // 变空的子树已经删除
+    let root = trie_st.root.as_ref().unwrap();
+    assert!(root.child('t' as usize).is_none());
+    assert!(root.child('s' as usize).unwrap().as_ref().unwrap().child('h' as usize).unwrap()
+        .as_ref().unwrap().child('o' as usize).is_none());
+
+    trie_st.retain(|_, _| false);
+    assert!(trie_st.root.is_none());
+}
+
+#[test]
+fn test_iter() {
+    let mut trie_st = TrieST::new();
+
+    for (i, key) in ["she", "sells", "sea", "shells", "by", "the", "sea", "shore", "", "s"].iter().enumerate() {
+        trie_st.put(key, i);
+    }
+
+    assert_eq!(trie_st.iter().collect::<Vec<_>>(), trie_st.to_vec());
+    assert_eq!(trie_st.iter().next(), Some((String::new(), &8)));
+    assert_eq!(trie_st.iter().map(|(key, _)| key).nth(2), Some(String::from("s")));
+
+    // 可以只取前几个
+    let mut iter = trie_st.iter();
+    assert_eq!(iter.next().map(|(key, _)| key), Some(String::new()));
+    assert_eq!(iter.next().map(|(key, _)| key), Some(String::from("by")));
+
+    let empty: TrieST<usize> = TrieST::new();
+    assert_eq!(empty.iter().next(), None);
+}
+
+#[test]
+fn test_rank_select() {
+    let mut trie_st = TrieST::new();
+
+    for (i, key) in ["she", "sells", "sea", "shells", "by", "the", "shore", "s"].iter().enumerate() {
+        trie_st.put(key, i);
+    }
+
+    let keys = trie_st.keys();
+
+    assert_eq!(trie_st.select(0), Some(String::from("by")));
+    assert_eq!(trie_st.select(7), Some(String::from("the")));
+    assert_eq!(trie_st.select(8), None);
+    assert_eq!(trie_st.rank("she"), 4);
+
+    for (i, key) in keys.iter().enumerate() {
+        assert_eq!(trie_st.rank(key), i);
+        assert_eq!(trie_st.select(i).as_ref(), Some(key));
+    }
+
+    // 不存在的键
+    assert_eq!(trie_st.rank(""), 0);
+    assert_eq!(trie_st.rank("a"), 0);
+    assert_eq!(trie_st.rank("se"), 2);
+    assert_eq!(trie_st.rank("shellsx"), 6);
+    assert_eq!(trie_st.rank("zzz"), 8);
+}
@@ -0,0 +1,226 @@
+// 三向单词查找树
+type Link<T> = Option<Box<Node<T>>>;
+
+#[derive(Debug)]
+struct Node<T> {
+    c: char,
+    val: Option<T>,
+    left: Link<T>,
+    mid: Link<T>,
+    right: Link<T>,
+}
+
+trait LinkMethods<T> {
+    fn new(c: char) -> Link<T>;
+    fn get(&self, key: &[char], d: usize) -> &Link<T>;
+    fn put(&mut self, key: &[char], val: T, d: usize);
+    fn collect(&self, pre: &str, q: &mut Vec<String>);
+}
+
+impl<T> LinkMethods<T> for Link<T> {
+    fn new(c: char) -> Self {
+        Some(Box::new(Node {
+            c,
+            val: None,
+            left: None,
+            mid: None,
+            right: None,
+        }))
+    }
+
+    fn get(&self, key: &[char], d: usize) -> &Self {
+        match *self {
+            None => self,
+            Some(ref boxed_node) => {
+                let c = key[d];
+
+                if c < boxed_node.c {
+                    boxed_node.left.get(key, d)
+                }
+                else if c > boxed_node.c {
+                    boxed_node.right.get(key, d)
+                }
+                else if d < key.len() - 1 {
+                    boxed_node.mid.get(key, d + 1)
+                }
+                else {
+                    self
+                }
+            }
+        }
+    }
+
+    fn put(&mut self, key: &[char], val: T, d: usize) {
+        let c = key[d];
+
+        if self.is_none() {
+            *self = Self::new(c);
+        }
+
+        let boxed_node = self.as_mut().unwrap();
+
+        if c < boxed_node.c {
+            boxed_node.left.put(key, val, d);
+        }
+        else if c > boxed_node.c {
+            boxed_node.right.put(key, val, d);
+        }
+        else if d < key.len() - 1 {
+            boxed_node.mid.put(key, val, d + 1);
+        }
+        else {
+            boxed_node.val = Some(val);
+        }
+    }
+
+    fn collect(&self, pre: &str, q: &mut Vec<String>) {
+        let boxed_node = match *self {
+            Some(ref boxed_node) => boxed_node,
+            None => return,
+        };
+
+        boxed_node.left.collect(pre, q);
+
+        let mut key = String::from(pre);
+        key.push(boxed_node.c);
+
+        if boxed_node.val.is_some() {
+            q.push(key.clone());
+        }
+
+        boxed_node.mid.collect(&key, q);
+        boxed_node.right.collect(pre, q);
+    }
+}
+
+#[derive(Debug)]
+pub struct TST<T> {
+    root: Link<T>,
+}
+
+impl<T> TST<T> {
+    pub fn new() -> Self {
+        TST { root: None }
+    }
+
+    pub fn get(&self, key: &str) -> &Option<T> {
+        let key: Vec<char> = key.chars().collect();
+
+        if key.is_empty() {
+            return &None
+        }
+
+        match *self.root.get(&key, 0) {
+            Some(ref boxed_node) => &boxed_node.val,
+            None => &None,
+        }
+    }
+
+    // 不支持空字符串作为键
+    pub fn put(&mut self, key: &str, val: T) {
+        let key: Vec<char> = key.chars().collect();
+
+        if ! key.is_empty() {
+            self.root.put(&key, val, 0);
+        }
+    }
+
+    // 查找所有键
+    pub fn keys(&self) -> Vec<String> {
+        self.keys_with_prefix("")
+    }
+
+    // 前缀匹配
+    pub fn keys_with_prefix(&self, pre: &str) -> Vec<String> {
+        let mut q = Vec::new();
+        let chars: Vec<char> = pre.chars().collect();
+
+        if chars.is_empty() {
+            self.root.collect(pre, &mut q);
+            return q
+        }
+
+        if let Some(ref boxed_node) = *self.root.get(&chars, 0) {
+            if boxed_node.val.is_some() {
+                q.push(String::from(pre));
+            }
+
+            boxed_node.mid.collect(pre, &mut q);
+        }
+
+        q
+    }
+
+    // 查询字符串的最长前缀键
+    pub fn longest_prefix_of(&self, query: &str) -> Option<String> {
+        let chars: Vec<char> = query.chars().collect();
+        let mut length = None;
+        let mut x = &self.root;
+        let mut d = 0;
+
+        while d < chars.len() {
+            let boxed_node = match *x {
+                Some(ref boxed_node) => boxed_node,
+                None => break,
+            };
+
+            let c = chars[d];
+
+            if c < boxed_node.c {
+                x = &boxed_node.left;
+            }
+            else if c > boxed_node.c {
+                x = &boxed_node.right;
+            }
+            else {
+                d += 1;
+
+                if boxed_node.val.is_some() {
+                    length = Some(d);
+                }
+
+                x = &boxed_node.mid;
+            }
+        }
+
+        length.map(|length| chars[..length].iter().collect())
+    }
+}
+
+impl<T> Default for TST<T> {
+    fn default() -> Self {
+        TST::new()
+    }
+}
+
+#[test]
+fn test() {
+    let mut tst = TST::new();
+
+    tst.put("abc", 1);
+    tst.put("cbd", 2);
+    tst.put("bde", 3);
+    tst.put("def", 4);
+    tst.put("abf", 5);
+
+    assert_eq!(tst.get("abc"), &Some(1));
+    assert_eq!(tst.get("cbd"), &Some(2));
+    assert_eq!(tst.get("bde"), &Some(3));
+    assert_eq!(tst.get("def"), &Some(4));
+    assert_eq!(tst.get("abf"), &Some(5));
+    assert_eq!(tst.get("ab"), &None);
+    assert_eq!(tst.get("abcd"), &None);
+    assert_eq!(tst.keys(), ["abc", "abf", "bde", "cbd", "def"]);
+    assert_eq!(tst.keys_with_prefix("ab"), ["abc", "abf"]);
+
+    // 任意 Unicode 字符
+    tst.put("算法", 6);
+    tst.put("算法导论", 7);
+
+    assert_eq!(tst.get("算法"), &Some(6));
+    assert_eq!(tst.keys_with_prefix("算"), ["算法", "算法导论"]);
+    assert_eq!(tst.longest_prefix_of("算法第四版"), Some(String::from("算法")));
+    assert_eq!(tst.longest_prefix_of("算法导论第三版"), Some(String::from("算法导论")));
+    assert_eq!(tst.longest_prefix_of("abcdef"), Some(String::from("abc")));
+    assert_eq!(tst.longest_prefix_of("xyz"), None);
+}
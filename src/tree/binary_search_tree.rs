@@ -1,4 +1,6 @@
 use std::mem;
+use std::borrow::Borrow;
+use std::cmp::Ordering;
 
 pub type Link<K, V> = Option<Box<Node<K, V>>>;
 
@@ -30,6 +32,14 @@ pub trait ST<K, V> {
     fn delete_self(&mut self);
 }
 
+trait LinkMethods<K, V> {
+    fn replace_or_insert(&mut self, key: K, val: V) -> Option<V>;
+    fn find_or_insert<F: FnOnce() -> V>(&mut self, key: K, f: F) -> (&mut V, bool);
+    fn in_order(&self) -> Vec<&Node<K, V>>;
+    fn recompute_sizes(&mut self) -> usize;
+    fn check_sizes(&self) -> bool;
+}
+
 
 impl<K: PartialOrd, V> ST<K, V> for Link<K, V> {
     fn new(key: K, val: V) -> Self {
@@ -80,32 +90,7 @@ impl<K: PartialOrd, V> ST<K, V> for Link<K, V> {
     }
 
     fn put(&mut self, key: K, val: V) {
-        match *self {
-            Some(ref mut node) => {
-                if key < node.key {
-                    node.left.put(key, val)
-                }
-                else if key > node.key {
-                    node.right.put(key, val)
-                }
-                else {
-                    node.val = val
-                }
-
-                node.n = node.left.size() + node.right.size() + 1
-            },
-            None => {
-                let node = Box::new(Node {
-                    key,
-                    val,
-                    left: None,
-                    right: None,
-                    n: 1,
-                });
-
-                *self = Some(node);
-            },
-        }
+        self.replace_or_insert(key, val);
     }
 
     fn min(&self) -> &Self {
@@ -310,6 +295,107 @@ impl<K: PartialOrd, V> ST<K, V> for Link<K, V> {
     }
 }
 
+impl<K: PartialOrd, V> LinkMethods<K, V> for Link<K, V> {
+    // 插入或更新，返回被替换的值，更新时子树大小不变
+    fn replace_or_insert(&mut self, key: K, val: V) -> Option<V> {
+        match *self {
+            Some(ref mut node) => {
+                let old = if key < node.key {
+                    node.left.replace_or_insert(key, val)
+                }
+                else if key > node.key {
+                    node.right.replace_or_insert(key, val)
+                }
+                else {
+                    Some(mem::replace(&mut node.val, val))
+                };
+
+                node.n = node.left.size() + node.right.size() + 1;
+                old
+            },
+            None => {
+                let node = Box::new(Node {
+                    key,
+                    val,
+                    left: None,
+                    right: None,
+                    n: 1,
+                });
+
+                *self = Some(node);
+                None
+            },
+        }
+    }
+
+    // 查找键，不存在时插入 f() 的结果，返回值和是否插入，插入时沿途的子树大小加一
+    fn find_or_insert<F: FnOnce() -> V>(&mut self, key: K, f: F) -> (&mut V, bool) {
+        match *self {
+            Some(ref mut node) => {
+                let (val, inserted) = match key.partial_cmp(&node.key) {
+                    Some(Ordering::Less) => node.left.find_or_insert(key, f),
+                    Some(Ordering::Greater) => node.right.find_or_insert(key, f),
+                    Some(Ordering::Equal) => return (&mut node.val, false),
+                    None => panic!("key cannot be compared"),
+                };
+
+                if inserted {
+                    node.n += 1;
+                }
+
+                (val, inserted)
+            },
+            None => {
+                *self = Self::new(key, f());
+                (&mut self.as_mut().unwrap().val, true)
+            },
+        }
+    }
+
+    // 中序遍历
+    fn in_order(&self) -> Vec<&Node<K, V>> {
+        let mut stack : Vec<&Node<K, V>> = Vec::new();
+        let mut res : Vec<&Node<K, V>> = Vec::new();
+        let mut p = self;
+
+        while p.is_some() || ! stack.is_empty() {
+            while let Some(ref node) = *p {
+                stack.push(node);
+                p = &node.left;
+            }
+
+            let cur = stack.pop().unwrap();
+            res.push(cur);
+            p = &cur.right;
+        }
+
+        res
+    }
+
+    // 由子节点重新计算子树大小，返回当前子树大小
+    fn recompute_sizes(&mut self) -> usize {
+        match *self {
+            Some(ref mut node) => {
+                node.n = node.left.recompute_sizes() + node.right.recompute_sizes() + 1;
+                node.n
+            },
+            None => 0,
+        }
+    }
+
+    // 检查每个子树大小是否等于左右子树大小之和加一
+    fn check_sizes(&self) -> bool {
+        match *self {
+            Some(ref node) => {
+                node.n == node.left.size() + node.right.size() + 1
+                    && node.left.check_sizes()
+                    && node.right.check_sizes()
+            },
+            None => true,
+        }
+    }
+}
+
 
 pub struct BinarySearchTree<K, V> {
     root: Link<K, V>,
@@ -325,13 +411,43 @@ impl<K: PartialOrd, V> BinarySearchTree<K, V> {
     }
 
     pub fn put(&mut self, key: K, val: V) {
-        self.root.put(key, val)
+        self.root.put(key, val);
+    }
+
+    // 与 put 相同，键已存在时返回原来的值
+    pub fn insert(&mut self, key: K, val: V) -> Option<V> {
+        self.root.replace_or_insert(key, val)
     }
 
     pub fn get(&self, key: K) -> &Link<K, V> {
         self.root.get(key)
     }
 
+    // 键不存在时才调用 f 计算并插入，返回树中的值
+    pub fn get_or_insert_with<F: FnOnce() -> V>(&mut self, key: K, f: F) -> &V {
+        self.root.find_or_insert(key, f).0
+    }
+
+    // 以借用的形式查找，例如 String 类型的键可以用 &str 查找
+    pub fn get_ref<Q>(&self, key: &Q) -> Option<&V> where K: Borrow<Q>, Q: ?Sized + PartialOrd {
+        let mut x = &self.root;
+
+        while let Some(ref node) = *x {
+            match key.partial_cmp(node.key.borrow()) {
+                Some(Ordering::Less) => x = &node.left,
+                Some(Ordering::Greater) => x = &node.right,
+                Some(Ordering::Equal) => return Some(&node.val),
+                None => break,
+            }
+        }
+
+        None
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool where K: Borrow<Q>, Q: ?Sized + PartialOrd {
+        self.get_ref(key).is_some()
+    }
+
     pub fn min(&self) -> &Link<K, V> {
         self.root.min()
     }
@@ -340,6 +456,16 @@ impl<K: PartialOrd, V> BinarySearchTree<K, V> {
         self.root.max()
     }
 
+    // 最小的键值对
+    pub fn first_key_value(&self) -> Option<(&K, &V)> {
+        self.root.min().as_ref().map(|node| (&node.key, &node.val))
+    }
+
+    // 最大的键值对
+    pub fn last_key_value(&self) -> Option<(&K, &V)> {
+        self.root.max().as_ref().map(|node| (&node.key, &node.val))
+    }
+
     pub fn floor(&self, key: K) -> &Link<K, V> {
         self.root.floor(key)
     }
@@ -348,6 +474,38 @@ impl<K: PartialOrd, V> BinarySearchTree<K, V> {
         self.root.ceiling(key)
     }
 
+    // 按 distance 计算距离，返回最接近 key 的键，距离相同时取较小的键
+    // 最接近的键只可能是 floor 或 ceiling，两者都在查找路径上，所以只需向下查找一次
+    pub fn closest_key_by<D: PartialOrd, F: Fn(&K, &K) -> D>(&self, key: K, distance: F) -> Option<&K> {
+        let mut best: Option<(&K, D)> = None;
+        let mut x = &self.root;
+
+        while let Some(ref node) = *x {
+            let d = distance(&node.key, &key);
+
+            let closer = match best {
+                Some((ref best_key, ref best_d)) => d < *best_d || (d == *best_d && node.key < **best_key),
+                None => true,
+            };
+
+            if closer {
+                best = Some((&node.key, d));
+            }
+
+            if key < node.key {
+                x = &node.left;
+            }
+            else if key > node.key {
+                x = &node.right;
+            }
+            else {
+                break
+            }
+        }
+
+        best.map(|(key, _)| key)
+    }
+
     pub fn select(&self, k: usize) -> &Link<K, V> {
         self.root.select(k)
     }
@@ -367,6 +525,99 @@ impl<K: PartialOrd, V> BinarySearchTree<K, V> {
     pub fn delete(&mut self, key: K) {
         self.root.delete(key)
     }
+
+    pub fn in_order(&self) -> Vec<&Node<K, V>> {
+        self.root.in_order()
+    }
+
+    // 按键由小到大遍历键值对
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.in_order().into_iter().map(|node| (&node.key, &node.val))
+    }
+
+    // 按键由小到大遍历键值对，值可修改，键只读以保证树的有序性
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&K, &mut V)> {
+        let mut iter = IterMut { stack: Vec::new() };
+        iter.push_left(&mut self.root);
+        iter
+    }
+
+    // 重新计算所有节点的子树大小
+    pub fn recompute_sizes(&mut self) {
+        self.root.recompute_sizes();
+    }
+
+    // 检查所有节点的子树大小是否正确
+    pub fn check_sizes(&self) -> bool {
+        self.root.check_sizes()
+    }
+
+    // 检查中序遍历的键严格递增，并且每个节点的子树大小正确，线性时间，用于测试
+    pub fn is_bst(&self) -> bool {
+        self.in_order().windows(2).all(|w| w[0].key < w[1].key) && self.check_sizes()
+    }
+
+    // 将另一棵树的所有键值对插入当前树，键相同时使用另一棵树的值
+    pub fn merge(&mut self, other: BinarySearchTree<K, V>) {
+        let mut stack: Vec<Box<Node<K, V>>> = other.root.into_iter().collect();
+
+        while let Some(mut boxed_node) = stack.pop() {
+            stack.extend(boxed_node.left.take());
+            stack.extend(boxed_node.right.take());
+
+            let node = *boxed_node;
+            self.put(node.key, node.val);
+        }
+    }
+}
+
+// 中序遍历的栈，保存节点的键、值和还未访问的右子树
+struct IterMut<'a, K: 'a, V: 'a> {
+    stack: Vec<(&'a K, &'a mut V, &'a mut Link<K, V>)>,
+}
+
+impl<'a, K: 'a, V: 'a> IterMut<'a, K, V> {
+    fn push_left(&mut self, mut link: &'a mut Link<K, V>) {
+        loop {
+            let node: &'a mut Node<K, V> = match *link {
+                Some(ref mut boxed_node) => boxed_node,
+                None => break,
+            };
+
+            let Node { ref key, ref mut val, ref mut left, ref mut right, .. } = *node;
+            self.stack.push((key, val, right));
+            link = left;
+        }
+    }
+}
+
+impl<'a, K: 'a, V: 'a> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<(&'a K, &'a mut V)> {
+        let (key, val, right) = self.stack.pop()?;
+        self.push_left(right);
+        Some((key, val))
+    }
+}
+
+impl<K: PartialOrd, V> Default for BinarySearchTree<K, V> {
+    fn default() -> Self {
+        BinarySearchTree::new()
+    }
+}
+
+// 按中序遍历的键值对比较两棵树，与树的形状无关
+impl<K: PartialOrd, V: PartialEq> PartialEq for BinarySearchTree<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        if self.size() != other.size() {
+            return false
+        }
+
+        self.in_order().iter().zip(other.in_order().iter()).all(|(a, b)| {
+            a.key == b.key && a.val == b.val
+        })
+    }
 }
 
 #[test]
@@ -441,4 +692,249 @@ fn test() {
     }
 
     assert_eq!(bst.size(), 5);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_first_last_key_value() {
+    let mut bst = BinarySearchTree::<&str, isize>::new();
+    assert_eq!(bst.first_key_value(), None);
+    assert_eq!(bst.last_key_value(), None);
+
+    bst.put("S", 1);
+    bst.put("E", 2);
+    bst.put("X", 3);
+    bst.put("A", 4);
+    bst.put("R", 5);
+    bst.put("C", 6);
+    bst.put("H", 7);
+    bst.put("M", 8);
+
+    assert_eq!(bst.first_key_value(), Some((&"A", &4)));
+    assert_eq!(bst.last_key_value(), Some((&"X", &3)));
+}
+
+#[test]
+fn test_eq() {
+    let data = [("S", 1), ("E", 2), ("X", 3), ("A", 4), ("R", 5), ("C", 6), ("H", 7), ("M", 8)];
+
+    let mut bst = BinarySearchTree::new();
+    let mut other = BinarySearchTree::new();
+
+    for &(key, val) in data.iter() {
+        bst.put(key, val);
+    }
+
+    for &(key, val) in data.iter().rev() {
+        other.put(key, val);
+    }
+
+    assert!(bst == other);
+
+    other.put("M", 9);
+    assert!(bst != other);
+
+    other.put("M", 8);
+    other.delete("A");
+    assert!(bst != other);
+}
+
+#[test]
+fn test_merge() {
+    let mut bst = BinarySearchTree::new();
+    let mut other = BinarySearchTree::new();
+
+    for &(key, val) in [("S", 1), ("E", 2), ("X", 3), ("A", 4)].iter() {
+        bst.put(key, val);
+    }
+
+    for &(key, val) in [("R", 5), ("C", 6), ("E", 20), ("H", 7), ("M", 8)].iter() {
+        other.put(key, val);
+    }
+
+    bst.merge(other);
+
+    assert_eq!(bst.size(), 8);
+
+    match *bst.get("E") {
+        Some(ref node) => assert_eq!(node.val, 20),
+        None => assert!(false),
+    }
+
+    // 子树的 n 正确时，rank 和 select 互为逆运算
+    for k in 0..bst.size() {
+        let key = bst.select(k).as_ref().unwrap().key;
+        assert_eq!(bst.rank(key), k);
+    }
+
+    let keys: Vec<&str> = bst.in_order().iter().map(|node| node.key).collect();
+    assert_eq!(keys, ["A", "C", "E", "H", "M", "R", "S", "X"]);
+}
+
+#[test]
+fn test_check_sizes() {
+    let mut bst = BinarySearchTree::new();
+
+    assert!(bst.check_sizes());
+
+    for (i, &key) in ["S", "E", "X", "A", "R", "C", "H", "M"].iter().enumerate() {
+        bst.put(key, i);
+    }
+
+    bst.delete("E");
+    assert!(bst.check_sizes());
+
+    // 人为破坏 E 所在位置的子树大小
+    bst.root.as_mut().unwrap().left.as_mut().unwrap().n = 100;
+    assert!(! bst.check_sizes());
+
+    bst.recompute_sizes();
+    assert!(bst.check_sizes());
+    assert_eq!(bst.size(), 7);
+    assert_eq!(bst.rank("M"), 3);
+    assert_eq!(bst.select(3).as_ref().unwrap().key, "M");
+}
+
+#[test]
+fn test_iter_mut() {
+    let mut bst = BinarySearchTree::new();
+
+    for (i, &key) in ["S", "E", "X", "A", "R", "C", "H", "M"].iter().enumerate() {
+        bst.put(key, i);
+    }
+
+    for (_, val) in bst.iter_mut() {
+        *val += 100;
+    }
+
+    let keys: Vec<&str> = bst.iter_mut().map(|(key, _)| *key).collect();
+    assert_eq!(keys, ["A", "C", "E", "H", "M", "R", "S", "X"]);
+
+    let entries: Vec<(&str, usize)> = bst.iter().map(|(key, val)| (*key, *val)).collect();
+    assert_eq!(entries, [("A", 103), ("C", 105), ("E", 101), ("H", 106), ("M", 107), ("R", 104), ("S", 100), ("X", 102)]);
+
+    let mut empty: BinarySearchTree<&str, usize> = BinarySearchTree::new();
+    assert_eq!(empty.iter_mut().count(), 0);
+}
+
+#[test]
+fn test_get_ref() {
+    let mut tree = BinarySearchTree::new();
+
+    for (i, &key) in ["S", "E", "X", "A", "R", "C", "H", "M"].iter().enumerate() {
+        tree.put(String::from(key), i);
+    }
+
+    assert_eq!(tree.get_ref("S"), Some(&0));
+    assert_eq!(tree.get_ref("M"), Some(&7));
+    assert_eq!(tree.get_ref("B"), None);
+    assert!(tree.contains_key("H"));
+    assert!(! tree.contains_key("Z"));
+
+    let key = String::from("A");
+    assert_eq!(tree.get_ref(&key), Some(&3));
+}
+
+#[test]
+fn test_default() {
+    let bst: BinarySearchTree<&str, usize> = Default::default();
+
+    assert_eq!(bst.size(), 0);
+    assert!(bst.min().is_none());
+}
+
+#[test]
+fn test_closest_key_by() {
+    let mut bst = BinarySearchTree::new();
+    let distance = |a: &i32, b: &i32| (a - b).abs();
+
+    assert_eq!(bst.closest_key_by(5, distance), None);
+
+    for &key in [50, 20, 80, 10, 30, 70, 90].iter() {
+        bst.put(key, ());
+    }
+
+    assert_eq!(bst.closest_key_by(33, distance), Some(&30));
+    assert_eq!(bst.closest_key_by(68, distance), Some(&70));
+    assert_eq!(bst.closest_key_by(70, distance), Some(&70));
+    assert_eq!(bst.closest_key_by(-100, distance), Some(&10));
+    assert_eq!(bst.closest_key_by(1000, distance), Some(&90));
+
+    // 距离相同时取较小的键
+    assert_eq!(bst.closest_key_by(25, distance), Some(&20));
+    assert_eq!(bst.closest_key_by(60, distance), Some(&50));
+}
+
+#[test]
+fn test_get_or_insert_with() {
+    use std::cell::Cell;
+
+    let mut bst = BinarySearchTree::new();
+    let calls = Cell::new(0);
+
+    for &key in ["S", "E", "X", "A", "S", "E", "R", "A"].iter() {
+        let val = *bst.get_or_insert_with(key, || {
+            calls.set(calls.get() + 1);
+            key.len() * 10 + calls.get()
+        });
+
+        assert_eq!(bst.get(key).as_ref().unwrap().val, val);
+    }
+
+    // 只有第一次出现的键会调用 f
+    assert_eq!(calls.get(), 5);
+    assert_eq!(bst.size(), 5);
+    assert!(bst.check_sizes());
+
+    assert_eq!(*bst.get_or_insert_with("S", || 0), 11);
+    assert_eq!(*bst.get_or_insert_with("R", || 0), 15);
+    assert_eq!(bst.rank("S"), 3);
+}
+
+#[test]
+fn test_insert() {
+    let mut bst = BinarySearchTree::new();
+
+    assert_eq!(bst.insert("S", 1), None);
+    assert_eq!(bst.insert("E", 2), None);
+    assert_eq!(bst.insert("X", 3), None);
+    assert_eq!(bst.size(), 3);
+
+    assert_eq!(bst.insert("E", 4), Some(2));
+    assert_eq!(bst.insert("E", 5), Some(4));
+    assert_eq!(bst.size(), 3);
+    assert_eq!(bst.get_ref("E"), Some(&5));
+    assert!(bst.check_sizes());
+}
+
+#[test]
+fn test_is_bst() {
+    use rand::{Rng, SeedableRng, XorShiftRng};
+
+    let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+    let mut bst = BinarySearchTree::new();
+    let mut present = vec![false; 64];
+
+    assert!(bst.is_bst());
+
+    for i in 0..2000 {
+        let key = rng.gen_range(0, 64);
+
+        // 插入和删除（包括不存在的键）各占一半
+        if rng.gen() {
+            bst.put(key, i);
+            present[key] = true;
+        }
+        else {
+            bst.delete(key);
+            present[key] = false;
+        }
+
+        assert!(bst.is_bst());
+        assert_eq!(bst.size(), present.iter().filter(|&&p| p).count());
+    }
+
+    // 人为破坏顺序
+    bst.put(100, 0);
+    bst.root.as_mut().unwrap().key = 1000;
+    assert!(! bst.is_bst());
+}
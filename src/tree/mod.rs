@@ -1,3 +1,4 @@
 pub mod binary_search_tree;
 pub mod avl_tree;
 pub mod red_black_tree;
+pub mod persistent_rbt;
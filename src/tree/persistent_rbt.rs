@@ -0,0 +1,294 @@
+use std::sync::Arc;
+use std::cmp::Ordering;
+
+// 持久化红黑树（左倾），节点由 Arc 共享
+// put 只复制根节点到插入位置路径上的节点，返回新版本，旧版本保持不变
+type Link<K, V> = Option<Arc<Node<K, V>>>;
+
+#[derive(Debug, Clone)]
+struct Node<K, V> {
+    key: K,
+    val: V,
+    n: usize,
+    red: bool,
+    left: Link<K, V>,
+    right: Link<K, V>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PersistentRbt<K, V> {
+    root: Link<K, V>,
+}
+
+impl<K: PartialOrd + Clone, V: Clone> PersistentRbt<K, V> {
+    pub fn new() -> Self {
+        PersistentRbt { root: None }
+    }
+
+    pub fn size(&self) -> usize {
+        size(&self.root)
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let mut x = &self.root;
+
+        while let Some(ref node) = *x {
+            match key.partial_cmp(&node.key) {
+                Some(Ordering::Less) => x = &node.left,
+                Some(Ordering::Greater) => x = &node.right,
+                Some(Ordering::Equal) => return Some(&node.val),
+                None => break,
+            }
+        }
+
+        None
+    }
+
+    // 由按键严格递增的键值对一次构建，O(n)
+    pub fn from_sorted(pairs: Vec<(K, V)>) -> Self {
+        let n = pairs.len();
+        let mut h = 0;
+
+        // 最大的黑色高度 h，满足 2^h - 1 <= n
+        while (1 << (h + 1)) - 1 <= n {
+            h += 1;
+        }
+
+        PersistentRbt { root: build(&mut pairs.into_iter(), n, h) }
+    }
+
+    // 插入或更新，返回新版本，当前版本不变
+    pub fn put(&self, key: K, val: V) -> Self {
+        let mut root = put(&self.root, key, val);
+        root.red = false;
+
+        PersistentRbt { root: Some(Arc::new(root)) }
+    }
+
+    // 按键由小到大排列的所有键
+    pub fn keys(&self) -> Vec<&K> {
+        let mut stack: Vec<&Node<K, V>> = Vec::new();
+        let mut res = Vec::with_capacity(self.size());
+        let mut p = &self.root;
+
+        while p.is_some() || ! stack.is_empty() {
+            while let Some(ref node) = *p {
+                stack.push(node);
+                p = &node.left;
+            }
+
+            let cur = stack.pop().unwrap();
+            res.push(&cur.key);
+            p = &cur.right;
+        }
+
+        res
+    }
+}
+
+impl<K: PartialOrd + Clone, V: Clone> Default for PersistentRbt<K, V> {
+    fn default() -> Self {
+        PersistentRbt::new()
+    }
+}
+
+fn size<K, V>(link: &Link<K, V>) -> usize {
+    match *link {
+        Some(ref node) => node.n,
+        None => 0,
+    }
+}
+
+fn is_red<K, V>(link: &Link<K, V>) -> bool {
+    match *link {
+        Some(ref node) => node.red,
+        None => false,
+    }
+}
+
+// 复制路径上的节点，子节点只增加引用计数
+fn put<K: PartialOrd + Clone, V: Clone>(link: &Link<K, V>, key: K, val: V) -> Node<K, V> {
+    let mut node = match *link {
+        Some(ref node) => (**node).clone(),
+        None => return Node { key, val, n: 1, red: true, left: None, right: None },
+    };
+
+    match key.partial_cmp(&node.key) {
+        Some(Ordering::Less) => {
+            let left = put(&node.left, key, val);
+            node.left = Some(Arc::new(left));
+        },
+        Some(Ordering::Greater) => {
+            let right = put(&node.right, key, val);
+            node.right = Some(Arc::new(right));
+        },
+        Some(Ordering::Equal) => node.val = val,
+        // 无法比较的键（例如 NaN）不插入
+        None => {},
+    }
+
+    balance(node)
+}
+
+// 按中序从 iter 中取出 n 个键值对，构建黑色高度为 h 的子树，即高度为 h 的 2-3 树
+// 高度为 h 的 2-3 树可以容纳 2^h - 1 到 3^h - 1 个键，2-结点容纳不下时使用 3-结点（左倾的红色链接）
+fn build<K, V, I: Iterator<Item = (K, V)>>(iter: &mut I, n: usize, h: u32) -> Link<K, V> {
+    if n == 0 {
+        return None
+    }
+
+    // 高度为 h - 1 的子树最多容纳的键数
+    let max = 3usize.saturating_pow(h - 1) - 1;
+
+    if n - 1 <= max.saturating_mul(2) {
+        let left = build(iter, (n - 1) / 2, h - 1);
+        let (key, val) = iter.next().unwrap();
+        let right = build(iter, n - 1 - (n - 1) / 2, h - 1);
+
+        Some(Arc::new(Node { key, val, n, red: false, left, right }))
+    }
+    else {
+        let m = n - 2;
+        let (a, b) = (m / 3, (m + 1) / 3);
+
+        let left = build(iter, a, h - 1);
+        let (key, val) = iter.next().unwrap();
+        let middle = build(iter, b, h - 1);
+        let red = Node { key, val, n: a + b + 1, red: true, left, right: middle };
+
+        let (key, val) = iter.next().unwrap();
+        let right = build(iter, m - a - b, h - 1);
+
+        Some(Arc::new(Node { key, val, n, red: false, left: Some(Arc::new(red)), right }))
+    }
+}
+
+fn balance<K: Clone, V: Clone>(mut h: Node<K, V>) -> Node<K, V> {
+    if is_red(&h.right) && ! is_red(&h.left) {
+        h = rotate_left(h);
+    }
+
+    if is_red(&h.left) && h.left.as_ref().map_or(false, |left| is_red(&left.left)) {
+        h = rotate_right(h);
+    }
+
+    if is_red(&h.left) && is_red(&h.right) {
+        flip_colors(&mut h);
+    }
+
+    h.n = size(&h.left) + size(&h.right) + 1;
+    h
+}
+
+fn rotate_left<K: Clone, V: Clone>(mut h: Node<K, V>) -> Node<K, V> {
+    let mut x = (**h.right.as_ref().unwrap()).clone();
+
+    h.right = x.left.take();
+    x.red = h.red;
+    x.n = h.n;
+    h.red = true;
+    h.n = size(&h.left) + size(&h.right) + 1;
+    x.left = Some(Arc::new(h));
+    x
+}
+
+fn rotate_right<K: Clone, V: Clone>(mut h: Node<K, V>) -> Node<K, V> {
+    let mut x = (**h.left.as_ref().unwrap()).clone();
+
+    h.left = x.right.take();
+    x.red = h.red;
+    x.n = h.n;
+    h.red = true;
+    h.n = size(&h.left) + size(&h.right) + 1;
+    x.right = Some(Arc::new(h));
+    x
+}
+
+// 子节点是共享的，变色前先复制
+fn flip_colors<K: Clone, V: Clone>(h: &mut Node<K, V>) {
+    h.red = ! h.red;
+
+    for link in [&mut h.left, &mut h.right].iter_mut() {
+        if let Some(node) = link.take() {
+            let mut node = (*node).clone();
+            node.red = ! node.red;
+            **link = Some(Arc::new(node));
+        }
+    }
+}
+
+#[test]
+fn test() {
+    let v0 = PersistentRbt::new();
+    let mut versions = vec![v0];
+
+    for (i, &key) in ["S", "E", "X", "A", "R", "C", "H", "M"].iter().enumerate() {
+        let next = versions[i].put(key, i);
+        versions.push(next);
+    }
+
+    // 每个版本只包含在它之前插入的键
+    for (i, version) in versions.iter().enumerate() {
+        assert_eq!(version.size(), i);
+    }
+
+    assert_eq!(versions[3].keys(), [&"E", &"S", &"X"]);
+    assert_eq!(versions[8].keys(), [&"A", &"C", &"E", &"H", &"M", &"R", &"S", &"X"]);
+    assert_eq!(versions[2].get(&"X"), None);
+    assert_eq!(versions[3].get(&"X"), Some(&2));
+
+    // 更新值不影响旧版本
+    let updated = versions[8].put("S", 100);
+    assert_eq!(updated.get(&"S"), Some(&100));
+    assert_eq!(versions[8].get(&"S"), Some(&0));
+    assert_eq!(updated.size(), 8);
+}
+
+#[test]
+fn test_incomparable_key() {
+    let v1 = PersistentRbt::default().put(1.0, "a").put(2.0, "b");
+    let v2 = v1.put(f64::NAN, "c");
+
+    assert_eq!(v2.size(), 2);
+    assert_eq!(v2.get(&1.0), Some(&"a"));
+    assert_eq!(v2.get(&2.0), Some(&"b"));
+}
+
+// 检查左倾红黑树的性质和子树大小，返回黑色高度
+#[cfg(test)]
+fn check<K, V>(link: &Link<K, V>) -> usize {
+    match *link {
+        Some(ref node) => {
+            assert!(! is_red(&node.right));
+            assert!(! (node.red && is_red(&node.left)));
+            assert_eq!(node.n, size(&node.left) + size(&node.right) + 1);
+
+            let height = check(&node.left);
+            assert_eq!(height, check(&node.right));
+
+            if node.red { height } else { height + 1 }
+        },
+        None => 0,
+    }
+}
+
+#[test]
+fn test_from_sorted() {
+    for n in 0..200 {
+        let tree = PersistentRbt::from_sorted((0..n).map(|i| (i, i * 10)).collect());
+
+        assert!(! is_red(&tree.root));
+        check(&tree.root);
+        assert_eq!(tree.size(), n);
+        assert!(tree.keys().into_iter().cloned().eq(0..n));
+
+        for i in 0..n {
+            assert_eq!(tree.get(&i), Some(&(i * 10)));
+        }
+
+        // 构建结果可以继续插入
+        let next = tree.put(n, n * 10).put(n + 1, 0);
+        check(&next.root);
+        assert_eq!(next.size(), n + 2);
+    }
+}
@@ -1,5 +1,10 @@
+use std::fmt;
+use std::borrow::Borrow;
 use std::mem;
+use std::ptr;
 use std::cmp::Ordering;
+use std::sync::Arc;
+use super::persistent_rbt::PersistentRbt;
 
 pub type Link<K, V> = Option<Box<Node<K, V>>>;
 
@@ -26,14 +31,16 @@ enum FlipType {
 
 trait LinkMethods<K, V> {
     fn new(key: K, val: V) -> Link<K, V>;
-    fn put(&mut self, key: K, val: V);
+    fn put(&mut self, key: K, val: V) -> Option<V>;
     fn get(&self, key: K) -> Option<&V>;
     fn delete(&mut self, key: K);
-    fn delete_min(&mut self);
-    fn delete_max(&mut self);
+    fn pop_min(&mut self) -> Option<(K, V)>;
+    fn pop_max(&mut self) -> Option<(K, V)>;
     fn size(&self) -> usize;
     fn update_size(&mut self);
     fn is_red(&self) -> bool;
+    fn height(&self) -> usize;
+    fn black_height(&self) -> usize;
     fn left(&self) -> &Link<K, V>;
     fn left_mut(&mut self) -> &mut Link<K, V>;
     fn right(&self) -> &Link<K, V>;
@@ -49,13 +56,17 @@ trait LinkMethods<K, V> {
     fn move_red_left(&mut self);
     fn move_red_right(&mut self);
     fn select(&self, k: usize) -> &Link<K, V>;
-    fn rank(&self, key: K) -> usize;
+    fn select_mut(&mut self, k: usize) -> Option<&mut V>;
+    fn rank(&self, key: &K) -> (bool, usize);
     fn floor(&self, key: K) -> &Link<K, V>;
     fn ceiling(&self, key: K) -> &Link<K, V>;
+    fn keys_between<'a>(&'a self, lo: &K, hi: &K, q: &mut Vec<&'a K>);
     fn pre_order(&self) -> Vec<&Node<K, V>>;
     fn in_order(&self) -> Vec<&Node<K, V>>;
     fn post_order(&self) -> Vec<&Node<K, V>>;
+    fn post_order_one_stack(&self) -> Vec<&Node<K, V>>;
     fn level_order(&self) -> Vec<&Node<K, V>>;
+    fn levels(&self) -> Vec<Vec<&Node<K, V>>>;
 }
 
 impl<K: PartialOrd, V> LinkMethods<K, V> for Link<K, V> {
@@ -72,17 +83,20 @@ impl<K: PartialOrd, V> LinkMethods<K, V> for Link<K, V> {
         Some(boxed_node)
     }
 
-    fn put(&mut self, key: K, val: V) {
-        match Self::compare_key(&key, &self) {
+    // 插入或更新，返回被替换的值
+    fn put(&mut self, key: K, val: V) -> Option<V> {
+        let old = match Self::compare_key(&key, &self) {
             Some(Ordering::Less) => self.left_mut().put(key, val),
             Some(Ordering::Greater) => self.right_mut().put(key, val),
-            Some(Ordering::Equal) => {
-                self.as_mut().map(|node| node.val = val);
+            Some(Ordering::Equal) => self.as_mut().map(|node| mem::replace(&mut node.val, val)),
+            None => {
+                *self = Self::new(key, val);
+                None
             },
-            None => *self = Self::new(key, val),
         };
 
         self.balance();
+        old
     }
 
     fn get(&self, key: K) -> Option<&V> {
@@ -132,7 +146,7 @@ impl<K: PartialOrd, V> LinkMethods<K, V> for Link<K, V> {
                             mem::swap(&mut node.val, &mut next.as_mut().unwrap().val);
                         }
 
-                        boxed_node.right.delete_min();
+                        boxed_node.right.pop_min();
 
                         *self = Some(boxed_node);
                     }
@@ -147,38 +161,40 @@ impl<K: PartialOrd, V> LinkMethods<K, V> for Link<K, V> {
         self.balance();
     }
 
-    fn delete_min(&mut self) {
+    fn pop_min(&mut self) -> Option<(K, V)> {
         if self.left().is_none() {
-            *self = None;
-            return
+            return self.take().map(|node| (node.key, node.val))
         }
 
         if ! self.left().is_red() && ! self.left().left().is_red() {
             self.move_red_left();
         }
 
-        self.left_mut().delete_min();
+        let entry = self.left_mut().pop_min();
 
         self.balance();
+
+        entry
     }
 
-    fn delete_max(&mut self) {
+    fn pop_max(&mut self) -> Option<(K, V)> {
         if self.left().is_red() {
             self.rotate_right();
         }
 
         if self.right().is_none() {
-            *self = None;
-            return
+            return self.take().map(|node| (node.key, node.val))
         }
 
         if ! self.right().is_red() && ! self.right().left().is_red() {
             self.move_red_right();
         }
 
-        self.right_mut().delete_max();
+        let entry = self.right_mut().pop_max();
 
         self.balance();
+
+        entry
     }
 
     fn size(&self) -> usize {
@@ -206,6 +222,27 @@ impl<K: PartialOrd, V> LinkMethods<K, V> for Link<K, V> {
         }
     }
 
+    // 最长路径上的节点数
+    fn height(&self) -> usize {
+        match *self {
+            Some(ref boxed_node) => 1 + boxed_node.left.height().max(boxed_node.right.height()),
+            None => 0,
+        }
+    }
+
+    // 到空链接路径上的黑色节点数，任意路径都应相同
+    fn black_height(&self) -> usize {
+        match *self {
+            Some(ref boxed_node) => {
+                let height = boxed_node.left.black_height();
+                debug_assert_eq!(height, boxed_node.right.black_height());
+
+                if self.is_red() { height } else { height + 1 }
+            },
+            None => 0,
+        }
+    }
+
     fn left(&self) -> &Self {
         &self.as_ref().unwrap().left
     }
@@ -401,12 +438,36 @@ impl<K: PartialOrd, V> LinkMethods<K, V> for Link<K, V> {
         }
     }
 
-    fn rank(&self, key: K) -> usize {
-        match Self::compare_key(&key, &self) {
+    // 排名为 k 的值的可变引用
+    fn select_mut(&mut self, k: usize) -> Option<&mut V> {
+        match *self {
+            Some(ref mut boxed_node) => {
+                let t = boxed_node.left.size();
+
+                if k < t {
+                    boxed_node.left.select_mut(k)
+                }
+                else if k > t {
+                    boxed_node.right.select_mut(k - t - 1)
+                }
+                else {
+                    Some(&mut boxed_node.val)
+                }
+            },
+            None => None,
+        }
+    }
+
+    // 键是否存在，以及小于键的键的数量，一次查找同时得到
+    fn rank(&self, key: &K) -> (bool, usize) {
+        match Self::compare_key(key, &self) {
             Some(Ordering::Less) => self.left().rank(key),
-            Some(Ordering::Greater) => self.left().size() + self.right().rank(key) + 1,
-            Some(Ordering::Equal) => self.left().size(),
-            None => 0,
+            Some(Ordering::Greater) => {
+                let (found, rank) = self.right().rank(key);
+                (found, self.left().size() + rank + 1)
+            },
+            Some(Ordering::Equal) => (true, self.left().size()),
+            None => (false, 0),
         }
     }
 
@@ -443,6 +504,26 @@ impl<K: PartialOrd, V> LinkMethods<K, V> for Link<K, V> {
         }
     }
 
+    // 中序遍历 [lo, hi] 范围内的键，跳过范围外的子树
+    fn keys_between<'a>(&'a self, lo: &K, hi: &K, q: &mut Vec<&'a K>) {
+        let boxed_node = match *self {
+            Some(ref boxed_node) => boxed_node,
+            None => return,
+        };
+
+        if *lo < boxed_node.key {
+            boxed_node.left.keys_between(lo, hi, q);
+        }
+
+        if *lo <= boxed_node.key && boxed_node.key <= *hi {
+            q.push(&boxed_node.key);
+        }
+
+        if boxed_node.key < *hi {
+            boxed_node.right.keys_between(lo, hi, q);
+        }
+    }
+
     // 前序遍历
     fn pre_order(&self) -> Vec<&Node<K, V>> {
         let mut stack : Vec<&Node<K, V>> = Vec::new();
@@ -519,6 +600,39 @@ impl<K: PartialOrd, V> LinkMethods<K, V> for Link<K, V> {
         rev
     }
 
+    // 后序遍历，单栈，记录上一个访问的节点，不需要反转结果
+    fn post_order_one_stack(&self) -> Vec<&Node<K, V>> {
+        let mut stack : Vec<&Node<K, V>> = Vec::new();
+        let mut res : Vec<&Node<K, V>> = Vec::with_capacity(self.size());
+        let mut last : Option<&Node<K, V>> = None;
+        let mut p = self;
+
+        loop {
+            while let Some(ref node) = *p {
+                stack.push(node);
+                p = &node.left;
+            }
+
+            let node = match stack.last() {
+                Some(node) => *node,
+                None => break,
+            };
+
+            // 右子树存在且还未访问，先访问右子树
+            match node.right {
+                Some(ref right) if last.map_or(true, |last| ! ptr::eq(last, &**right)) => {
+                    p = &node.right;
+                },
+                _ => {
+                    res.push(node);
+                    last = stack.pop();
+                },
+            }
+        }
+
+        res
+    }
+
     // 层级遍历
     fn level_order(&self) -> Vec<&Node<K, V>> {
         use std::collections::VecDeque;
@@ -545,6 +659,39 @@ impl<K: PartialOrd, V> LinkMethods<K, V> for Link<K, V> {
 
         res
     }
+
+    // 按层分组的层级遍历，每次处理队列中同一层的全部节点
+    fn levels(&self) -> Vec<Vec<&Node<K, V>>> {
+        use std::collections::VecDeque;
+
+        let mut queue : VecDeque<&Node<K, V>> = VecDeque::new();
+        let mut res: Vec<Vec<&Node<K, V>>> = Vec::new();
+
+        if let Some(ref root) = *self {
+            queue.push_back(root);
+        }
+
+        while ! queue.is_empty() {
+            let mut level = Vec::with_capacity(queue.len());
+
+            for _ in 0..queue.len() {
+                let node = queue.pop_front().unwrap();
+                level.push(node);
+
+                if let Some(ref left) = node.left {
+                    queue.push_back(left);
+                }
+
+                if let Some(ref right) = node.right {
+                    queue.push_back(right);
+                }
+            }
+
+            res.push(level);
+        }
+
+        res
+    }
 }
 
 
@@ -559,13 +706,59 @@ impl<K: PartialOrd, V> RedBlackTree<K, V> {
     }
 
     pub fn put(&mut self, key: K, val: V) {
-        self.root.put(key, val);
+        self.insert(key, val);
+    }
+
+    // 与 put 相同，键已存在时返回原来的值
+    pub fn insert(&mut self, key: K, val: V) -> Option<V> {
+        let old = self.root.put(key, val);
+        self.root.as_mut().map(|node| node.color = Colors::BLACK);
+        old
     }
 
     pub fn get(&self, key: K) -> Option<&V> {
         self.root.get(key)
     }
 
+    // 以借用的形式查找，例如 String 类型的键可以用 &str 查找
+    pub fn get_ref<Q>(&self, key: &Q) -> Option<&V> where K: Borrow<Q>, Q: ?Sized + PartialOrd {
+        let mut x = &self.root;
+
+        while let Some(ref node) = *x {
+            match key.partial_cmp(node.key.borrow()) {
+                Some(Ordering::Less) => x = &node.left,
+                Some(Ordering::Greater) => x = &node.right,
+                Some(Ordering::Equal) => return Some(&node.val),
+                None => break,
+            }
+        }
+
+        None
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool where K: Borrow<Q>, Q: ?Sized + PartialOrd {
+        self.get_ref(key).is_some()
+    }
+
+    // 批量查找，结果与 keys 按位置一一对应，重复的键不去重，各自返回一次结果
+    pub fn get_many<'a>(&'a self, keys: &[K]) -> Vec<Option<&'a V>> {
+        keys.iter().map(|key| self.get_ref(key)).collect()
+    }
+
+    // 键对应的位置，用于读取、修改或插入
+    // 借用规则不允许在同一次查找中既可能返回值的引用又保留整棵树，所以不是一次查找：
+    // 先查找一次得到键是否存在和排名，再按排名取值，键存在时共 2 次 O(log n) 的查找，插入时共 3 次
+    pub fn entry<'a>(&'a mut self, key: K) -> Entry<'a, K, V> {
+        let (occupied, rank) = self.root.rank(&key);
+
+        if occupied {
+            Entry::Occupied(self.root.select_mut(rank).unwrap())
+        }
+        else {
+            Entry::Vacant(VacantEntry { tree: self, key, rank })
+        }
+    }
+
     pub fn delete(&mut self, key: K) {
         if ! self.root.left().is_red() && ! self.root.right().is_red() {
             self.root.as_mut().map(|node| node.color = Colors::RED);
@@ -579,33 +772,65 @@ impl<K: PartialOrd, V> RedBlackTree<K, V> {
     }
 
     pub fn delete_min(&mut self) {
+        self.pop_min();
+    }
+
+    pub fn delete_max(&mut self) {
+        self.pop_max();
+    }
+
+    // 删除并返回最小的键值对
+    pub fn pop_min(&mut self) -> Option<(K, V)> {
+        if self.root.is_none() {
+            return None
+        }
+
         if ! self.root.left().is_red() && ! self.root.right().is_red() {
             self.root.as_mut().map(|node| node.color = Colors::RED);
         }
 
-        self.root.delete_min();
+        let entry = self.root.pop_min();
 
         if self.root.size() > 0 {
             self.root.as_mut().map(|node| node.color = Colors::BLACK);
         }
+
+        entry
     }
 
-    pub fn delete_max(&mut self) {
+    // 删除并返回最大的键值对
+    pub fn pop_max(&mut self) -> Option<(K, V)> {
+        if self.root.is_none() {
+            return None
+        }
+
         if ! self.root.left().is_red() && ! self.root.right().is_red() {
             self.root.as_mut().map(|node| node.color = Colors::RED);
         }
 
-        self.root.delete_max();
+        let entry = self.root.pop_max();
 
         if self.root.size() > 0 {
             self.root.as_mut().map(|node| node.color = Colors::BLACK);
         }
+
+        entry
     }
 
     pub fn size(&self) -> usize {
         self.root.size()
     }
 
+    // 树高，根节点到最远叶子节点的边数
+    pub fn height(&self) -> usize {
+        self.root.height().saturating_sub(1)
+    }
+
+    // 黑色高度，根节点到任意空链接路径上的黑色节点数（包括根节点）
+    pub fn black_height(&self) -> usize {
+        self.root.black_height()
+    }
+
     pub fn min(&self) -> &Link<K, V> {
         self.root.min()
     }
@@ -614,12 +839,49 @@ impl<K: PartialOrd, V> RedBlackTree<K, V> {
         self.root.max()
     }
 
+    // 最小的键值对
+    pub fn first_key_value(&self) -> Option<(&K, &V)> {
+        self.root.min().as_ref().map(|node| (&node.key, &node.val))
+    }
+
+    // 最大的键值对
+    pub fn last_key_value(&self) -> Option<(&K, &V)> {
+        self.root.max().as_ref().map(|node| (&node.key, &node.val))
+    }
+
     pub fn select(&self, k: usize) -> &Link<K, V> {
         self.root.select(k)
     }
 
+    // 排名为 k 的键值对，k 不小于键的数量时返回 None
+    pub fn select_entry(&self, k: usize) -> Option<(&K, &V)> {
+        if k >= self.size() {
+            return None
+        }
+
+        self.root.select(k).as_ref().map(|node| (&node.key, &node.val))
+    }
+
     pub fn rank(&self, key: K) -> usize {
-        self.root.rank(key)
+        self.root.rank(&key).1
+    }
+
+    // 排名与 key 最接近的 k 个键，以 rank(key) 为中心，靠近两端时向内平移
+    pub fn neighbors_by_rank(&self, key: K, k: usize) -> Vec<&K> {
+        let size = self.size();
+        let start = self.rank(key).saturating_sub(k / 2).min(size.saturating_sub(k));
+        let end = (start + k).min(size);
+
+        (start..end).filter_map(|i| self.select_entry(i)).map(|(key, _)| key).collect()
+    }
+
+    // 中位数，键的数量为偶数时取较小的一个
+    pub fn median(&self) -> Option<(&K, &V)> {
+        if self.size() == 0 {
+            return None
+        }
+
+        self.select_entry((self.size() - 1) / 2)
     }
 
     pub fn floor(&self, key: K) -> &Link<K, V> {
@@ -630,6 +892,13 @@ impl<K: PartialOrd, V> RedBlackTree<K, V> {
         self.root.ceiling(key)
     }
 
+    // 从大于等于 lo 的最小键到小于等于 hi 的最大键，包含两端，lo 和 hi 不必是已有的键
+    pub fn keys_between(&self, lo: K, hi: K) -> Vec<&K> {
+        let mut q = Vec::new();
+        self.root.keys_between(&lo, &hi, &mut q);
+        q
+    }
+
     pub fn pre_order(&self) -> Vec<&Node<K, V>> {
         self.root.pre_order()
     }
@@ -642,11 +911,296 @@ impl<K: PartialOrd, V> RedBlackTree<K, V> {
         self.root.post_order()
     }
 
+    pub fn post_order_one_stack(&self) -> Vec<&Node<K, V>> {
+        self.root.post_order_one_stack()
+    }
+
     pub fn level_order(&self) -> Vec<&Node<K, V>> {
         self.root.level_order()
     }
+
+    // 按层分组的节点，根节点在第 0 层
+    pub fn levels(&self) -> Vec<Vec<&Node<K, V>>> {
+        self.root.levels()
+    }
+
+    // 两棵树都有的键，合并两个有序的键序列，线性时间
+    pub fn intersection<'a>(&'a self, other: &'a RedBlackTree<K, V>) -> Vec<&'a K> {
+        let a = self.in_order();
+        let b = other.in_order();
+        let (mut i, mut j) = (0, 0);
+        let mut res = Vec::new();
+
+        while i < a.len() && j < b.len() {
+            if a[i].key < b[j].key {
+                i += 1;
+            }
+            else if a[i].key > b[j].key {
+                j += 1;
+            }
+            else {
+                res.push(&a[i].key);
+                i += 1;
+                j += 1;
+            }
+        }
+
+        res
+    }
+
+    // 只在当前树中的键，合并两个有序的键序列，线性时间
+    pub fn difference<'a>(&'a self, other: &'a RedBlackTree<K, V>) -> Vec<&'a K> {
+        let a = self.in_order();
+        let b = other.in_order();
+        let (mut i, mut j) = (0, 0);
+        let mut res = Vec::new();
+
+        while i < a.len() {
+            if j == b.len() || a[i].key < b[j].key {
+                res.push(&a[i].key);
+                i += 1;
+            }
+            else if a[i].key > b[j].key {
+                j += 1;
+            }
+            else {
+                i += 1;
+                j += 1;
+            }
+        }
+
+        res
+    }
+
+    // 按键由小到大遍历值的可变引用，键不可修改，所以不需要重新平衡
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut V> {
+        let mut iter = ValuesMut { stack: Vec::new() };
+        iter.push_left(&mut self.root);
+        iter
+    }
+}
+
+impl<K: PartialOrd + Clone, V: Clone> RedBlackTree<K, V> {
+    // 当前内容的只读快照，之后对树的修改不影响快照
+    // 当前树的节点由 Box 独占，无法与快照共享，所以每次调用都按中序复制所有键值对，一次构建，O(n)
+    // 需要频繁保存版本时直接使用 PersistentRbt，它的 put 返回新版本，各版本之间共享未修改的节点
+    pub fn snapshot(&self) -> Arc<PersistentRbt<K, V>> {
+        let pairs = self.in_order().into_iter().map(|node| (node.key.clone(), node.val.clone())).collect();
+
+        Arc::new(PersistentRbt::from_sorted(pairs))
+    }
+}
+
+// 由 entry 返回，键已存在时持有值的可变引用，不存在时持有插入所需的信息
+pub enum Entry<'a, K: 'a, V: 'a> {
+    Occupied(&'a mut V),
+    Vacant(VacantEntry<'a, K, V>),
+}
+
+pub struct VacantEntry<'a, K: 'a, V: 'a> {
+    tree: &'a mut RedBlackTree<K, V>,
+    key: K,
+    rank: usize,    // 插入后键的排名
+}
+
+impl<'a, K: PartialOrd, V> Entry<'a, K, V> {
+    // 键不存在时插入 default，返回值的可变引用
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        match self {
+            Entry::Occupied(val) => val,
+            Entry::Vacant(entry) => entry.insert(default),
+        }
+    }
+
+    // 键不存在时插入 f 的返回值，只在需要时调用 f
+    pub fn or_insert_with<F: FnOnce() -> V>(self, f: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(val) => val,
+            Entry::Vacant(entry) => entry.insert(f()),
+        }
+    }
+
+    // 键存在时修改值
+    pub fn and_modify<F: FnOnce(&mut V)>(self, f: F) -> Self {
+        match self {
+            Entry::Occupied(val) => {
+                f(val);
+                Entry::Occupied(val)
+            },
+            entry @ Entry::Vacant(_) => entry,
+        }
+    }
+}
+
+impl<'a, K: PartialOrd, V> VacantEntry<'a, K, V> {
+    // 旋转不改变键的中序位置，所以插入后键的排名就是查找时得到的排名，按排名找到新插入的值
+    pub fn insert(self, val: V) -> &'a mut V {
+        let tree = self.tree;
+        tree.put(self.key, val);
+        tree.root.select_mut(self.rank).unwrap()
+    }
+}
+
+impl<K: PartialOrd + Clone, V> RedBlackTree<K, V> {
+    // 删除 [lo, hi] 范围内的所有键，返回删除的数量，每次删除后仍然平衡
+    pub fn delete_range(&mut self, lo: K, hi: K) -> usize {
+        let keys: Vec<K> = self.keys_between(lo, hi).into_iter().cloned().collect();
+        let n = keys.len();
+
+        for key in keys {
+            self.delete(key);
+        }
+
+        n
+    }
 }
 
+impl<K: PartialOrd, V> Default for RedBlackTree<K, V> {
+    fn default() -> Self {
+        RedBlackTree::new()
+    }
+}
+
+impl<K: PartialOrd + fmt::Debug, V> RedBlackTree<K, V> {
+    // 检查红黑树的所有性质，返回第一个不满足的性质
+    pub fn validate(&self) -> Result<(), String> {
+        validate_link(&self.root, None, None).map(|_| ())
+    }
+
+    // 不满足红黑树的性质时 panic，线性时间，可以在测试中每次修改后调用
+    pub fn assert_invariants(&self) {
+        if let Err(err) = self.validate() {
+            panic!("red-black tree invariant violated: {}", err);
+        }
+
+        assert!(! self.root.is_red(), "red-black tree invariant violated: root is red");
+    }
+}
+
+impl<K: PartialOrd + fmt::Display, V> RedBlackTree<K, V> {
+    // 以缩进的树形文本展示树的结构，每个节点一行，[R] 为红色，[B] 为黑色，先左后右
+    pub fn to_pretty_string(&self) -> String {
+        let mut res = String::new();
+
+        if let Some(ref root) = self.root {
+            res.push_str(&format!("{} {}\n", root.key, color_marker(&self.root)));
+            pretty_children(root, "", &mut res);
+        }
+
+        res
+    }
+}
+
+fn color_marker<K: PartialOrd, V>(link: &Link<K, V>) -> &'static str {
+    if link.is_red() { "[R]" } else { "[B]" }
+}
+
+fn pretty_children<K: PartialOrd + fmt::Display, V>(node: &Node<K, V>, prefix: &str, res: &mut String) {
+    let children: Vec<&Link<K, V>> = [&node.left, &node.right].iter()
+        .cloned()
+        .filter(|link| link.is_some())
+        .collect();
+
+    for (i, link) in children.iter().enumerate() {
+        let last = i == children.len() - 1;
+        let child = link.as_ref().unwrap();
+        let connector = if last { "└── " } else { "├── " };
+
+        res.push_str(&format!("{}{}{} {}\n", prefix, connector, child.key, color_marker(link)));
+        pretty_children(child, &format!("{}{}", prefix, if last { "    " } else { "│   " }), res);
+    }
+}
+
+// 检查子树，键必须在 (lo, hi) 范围内，返回子树的黑色高度
+fn validate_link<K: PartialOrd + fmt::Debug, V>(link: &Link<K, V>, lo: Option<&K>, hi: Option<&K>) -> Result<usize, String> {
+    let node = match *link {
+        Some(ref boxed_node) => boxed_node,
+        None => return Ok(0),
+    };
+
+    if lo.map_or(false, |lo| node.key <= *lo) || hi.map_or(false, |hi| node.key >= *hi) {
+        return Err(format!("key {:?} is out of order, expected between {:?} and {:?}", node.key, lo, hi))
+    }
+
+    if node.right.is_red() {
+        return Err(format!("node {:?} has a red right link", node.key))
+    }
+
+    if link.is_red() && node.left.is_red() {
+        return Err(format!("node {:?} and its left child are both red", node.key))
+    }
+
+    let size = node.left.size() + node.right.size() + 1;
+
+    if node.n != size {
+        return Err(format!("node {:?} has size {}, expected {}", node.key, node.n, size))
+    }
+
+    let left = validate_link(&node.left, lo, Some(&node.key))?;
+    let right = validate_link(&node.right, Some(&node.key), hi)?;
+
+    if left != right {
+        return Err(format!("node {:?} has black height {} on the left and {} on the right", node.key, left, right))
+    }
+
+    Ok(if link.is_red() { left } else { left + 1 })
+}
+
+// 中序遍历的栈，保存节点的值和还未访问的右子树
+struct ValuesMut<'a, K: 'a, V: 'a> {
+    stack: Vec<(&'a mut V, &'a mut Link<K, V>)>,
+}
+
+impl<'a, K: 'a, V: 'a> ValuesMut<'a, K, V> {
+    fn push_left(&mut self, mut link: &'a mut Link<K, V>) {
+        loop {
+            let node: &'a mut Node<K, V> = match *link {
+                Some(ref mut boxed_node) => boxed_node,
+                None => break,
+            };
+
+            let Node { ref mut val, ref mut left, ref mut right, .. } = *node;
+            self.stack.push((val, right));
+            link = left;
+        }
+    }
+}
+
+impl<'a, K: 'a, V: 'a> Iterator for ValuesMut<'a, K, V> {
+    type Item = &'a mut V;
+
+    fn next(&mut self) -> Option<&'a mut V> {
+        let (val, right) = self.stack.pop()?;
+        self.push_left(right);
+        Some(val)
+    }
+}
+
+// 按中序遍历的键值对比较两棵树，与树的形状和颜色无关
+impl<K: PartialOrd, V: PartialEq> PartialEq for RedBlackTree<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        if self.size() != other.size() {
+            return false
+        }
+
+        self.in_order().iter().zip(other.in_order().iter()).all(|(a, b)| {
+            a.key == b.key && a.val == b.val
+        })
+    }
+}
+
+
+// 测试用的标准示例，依次插入 S E X A R C H M，值为插入顺序
+#[cfg(test)]
+fn standard_tree() -> RedBlackTree<&'static str, usize> {
+    let mut tree = RedBlackTree::new();
+
+    for (i, &key) in ["S", "E", "X", "A", "R", "C", "H", "M"].iter().enumerate() {
+        tree.put(key, i);
+    }
+
+    tree
+}
 
 #[test]
 fn test() {
@@ -718,4 +1272,527 @@ fn test() {
     assert!(tree.get("S").is_none());
 
     tree.pre_order();
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_first_last_key_value() {
+    let mut tree = RedBlackTree::<&str, isize>::new();
+    assert_eq!(tree.first_key_value(), None);
+    assert_eq!(tree.last_key_value(), None);
+
+    tree.put("S", 1);
+    tree.put("E", 2);
+    tree.put("X", 3);
+    tree.put("A", 4);
+    tree.put("R", 5);
+    tree.put("C", 6);
+    tree.put("H", 7);
+    tree.put("M", 8);
+
+    assert_eq!(tree.first_key_value(), Some((&"A", &4)));
+    assert_eq!(tree.last_key_value(), Some((&"X", &3)));
+}
+
+#[test]
+fn test_pop_min_max() {
+    let mut tree = RedBlackTree::<&str, isize>::new();
+    assert_eq!(tree.pop_min(), None);
+    assert_eq!(tree.pop_max(), None);
+
+    tree.put("S", 1);
+    tree.put("E", 2);
+    tree.put("X", 3);
+    tree.put("A", 4);
+    tree.put("R", 5);
+    tree.put("C", 6);
+    tree.put("H", 7);
+    tree.put("M", 8);
+
+    assert_eq!(tree.pop_max(), Some(("X", 3)));
+    assert_eq!(tree.size(), 7);
+
+    let mut keys = Vec::new();
+
+    while let Some((key, _)) = tree.pop_min() {
+        keys.push(key);
+    }
+
+    assert_eq!(keys, ["A", "C", "E", "H", "M", "R", "S"]);
+    assert_eq!(tree.size(), 0);
+}
+
+#[test]
+fn test_eq() {
+    let data = [("S", 1), ("E", 2), ("X", 3), ("A", 4), ("R", 5), ("C", 6), ("H", 7), ("M", 8)];
+
+    let mut tree = RedBlackTree::new();
+    let mut other = RedBlackTree::new();
+
+    for &(key, val) in data.iter() {
+        tree.put(key, val);
+    }
+
+    for &(key, val) in data.iter().rev() {
+        other.put(key, val);
+    }
+
+    assert_eq!(tree, other);
+
+    other.put("M", 9);
+    assert!(tree != other);
+
+    other.put("M", 8);
+    other.delete("A");
+    assert!(tree != other);
+}
+
+#[test]
+fn test_values_mut() {
+    let mut tree = standard_tree();
+
+    for val in tree.values_mut() {
+        *val *= 2;
+    }
+
+    assert_eq!(tree.get("S"), Some(&0));
+    assert_eq!(tree.get("E"), Some(&2));
+    assert_eq!(tree.get("M"), Some(&14));
+
+    // 按键由小到大：A C E H M R S X
+    let vals: Vec<usize> = tree.values_mut().map(|val| *val).collect();
+    assert_eq!(vals, [6, 10, 2, 12, 14, 8, 0, 4]);
+}
+
+#[test]
+fn test_intersection_difference() {
+    let mut tree = RedBlackTree::new();
+    let mut other = RedBlackTree::new();
+
+    for &key in ["S", "E", "X", "A", "R", "C", "H", "M"].iter() {
+        tree.put(key, 0);
+    }
+
+    for &key in ["B", "C", "H", "Z", "S", "D"].iter() {
+        other.put(key, 1);
+    }
+
+    assert_eq!(tree.intersection(&other), [&"C", &"H", &"S"]);
+    assert_eq!(tree.difference(&other), [&"A", &"E", &"M", &"R", &"X"]);
+    assert_eq!(other.difference(&tree), [&"B", &"D", &"Z"]);
+
+    let empty = RedBlackTree::new();
+    assert!(tree.intersection(&empty).is_empty());
+    assert_eq!(tree.difference(&empty).len(), 8);
+}
+
+#[test]
+fn test_root_black() {
+    let mut tree = RedBlackTree::new();
+
+    // 新节点是红色的，只有一个节点时根节点曾经是红色
+    tree.put("S", 0);
+    assert!(! tree.root.is_red());
+
+    for (i, &key) in ["E", "X", "A", "R", "C", "H", "M"].iter().enumerate() {
+        tree.put(key, i + 1);
+        assert!(! tree.root.is_red());
+    }
+}
+
+#[test]
+fn test_height() {
+    let mut tree = RedBlackTree::new();
+
+    assert_eq!(tree.height(), 0);
+    assert_eq!(tree.black_height(), 0);
+
+    tree.put(0, 0);
+    assert_eq!(tree.height(), 0);
+    assert_eq!(tree.black_height(), 1);
+
+    for i in 1..31 {
+        tree.put(i, i);
+    }
+
+    // 有序插入 31 个键，得到一棵完全平衡的树
+    assert_eq!(tree.height(), 4);
+    assert_eq!(tree.black_height(), 5);
+
+    tree.delete_min();
+    tree.delete_max();
+    tree.delete(15);
+    assert!(tree.height() < 2 * tree.black_height());
+    assert_eq!(tree.black_height(), 4);
+}
+
+#[test]
+fn test_validate() {
+    let mut tree = RedBlackTree::new();
+
+    assert_eq!(tree.validate(), Ok(()));
+
+    for (i, &key) in ["S", "E", "A", "R", "C", "H", "X", "M", "P", "L"].iter().enumerate() {
+        tree.put(key, i);
+        assert_eq!(tree.validate(), Ok(()));
+    }
+
+    tree.delete("E");
+    tree.delete_min();
+    tree.put("B", 10);
+    tree.delete_max();
+    tree.delete("R");
+    tree.put("Z", 11);
+
+    assert_eq!(tree.validate(), Ok(()));
+
+    // 人为破坏子树大小
+    tree.root.as_mut().map(|node| node.n += 1);
+    assert!(tree.validate().unwrap_err().contains("size"));
+}
+
+#[test]
+fn test_keys_between() {
+    let tree = standard_tree();
+
+    // 边界不是已有的键
+    assert_eq!(tree.keys_between("B", "N"), [&"C", &"E", &"H", &"M"]);
+    assert_eq!(tree.keys_between("D", "Q"), [&"E", &"H", &"M"]);
+    assert_eq!(tree.keys_between("0", "Z").len(), 8);
+
+    // 边界是已有的键时包含两端
+    assert_eq!(tree.keys_between("E", "S"), [&"E", &"H", &"M", &"R", &"S"]);
+    assert_eq!(tree.keys_between("H", "H"), [&"H"]);
+
+    // 范围内没有键
+    assert!(tree.keys_between("T", "W").is_empty());
+    assert!(tree.keys_between("N", "B").is_empty());
+}
+
+#[test]
+fn test_median() {
+    let mut tree = RedBlackTree::new();
+
+    assert_eq!(tree.median(), None);
+
+    // A C E H M R S X
+    tree = standard_tree();
+
+    assert_eq!(tree.median(), Some((&"H", &6)));
+
+    tree.put("Z", 8);
+    assert_eq!(tree.median(), Some((&"M", &7)));
+
+    tree.delete_min();
+    tree.delete_min();
+    assert_eq!(tree.median(), Some((&"R", &4)));
+}
+
+#[test]
+fn test_select_entry() {
+    let mut tree = RedBlackTree::new();
+
+    assert_eq!(tree.select_entry(0), None);
+
+    tree = standard_tree();
+
+    assert_eq!(tree.select_entry(0), Some((&"A", &3)));
+    assert_eq!(tree.select_entry(3), Some((&"H", &6)));
+    assert_eq!(tree.select_entry(7), Some((&"X", &2)));
+    assert_eq!(tree.select_entry(tree.size()), None);
+    assert_eq!(tree.select_entry(100), None);
+
+    for k in 0..tree.size() {
+        let (&key, _) = tree.select_entry(k).unwrap();
+        assert_eq!(tree.rank(key), k);
+    }
+}
+
+#[test]
+fn test_post_order_one_stack() {
+    let mut tree = RedBlackTree::new();
+
+    assert!(tree.post_order_one_stack().is_empty());
+
+    tree = standard_tree();
+
+    let keys: Vec<&str> = tree.post_order_one_stack().iter().map(|node| node.key).collect();
+    let expected: Vec<&str> = tree.post_order().iter().map(|node| node.key).collect();
+
+    assert_eq!(keys, expected);
+    assert_eq!(keys.len(), 8);
+
+    for i in 0..100 {
+        tree.put(&"abcdefghijklmnopqrstuvwxyz"[i % 26..], i);
+    }
+
+    let keys: Vec<&str> = tree.post_order_one_stack().iter().map(|node| node.key).collect();
+    let expected: Vec<&str> = tree.post_order().iter().map(|node| node.key).collect();
+
+    assert_eq!(keys, expected);
+}
+
+#[test]
+fn test_get_ref() {
+    let mut tree = RedBlackTree::new();
+
+    for (i, &key) in ["S", "E", "X", "A", "R", "C", "H", "M"].iter().enumerate() {
+        tree.put(String::from(key), i);
+    }
+
+    assert_eq!(tree.get_ref("S"), Some(&0));
+    assert_eq!(tree.get_ref("M"), Some(&7));
+    assert_eq!(tree.get_ref("B"), None);
+    assert!(tree.contains_key("H"));
+    assert!(! tree.contains_key("Z"));
+
+    let key = String::from("A");
+    assert_eq!(tree.get_ref(&key), Some(&3));
+}
+
+#[test]
+fn test_default() {
+    let tree: RedBlackTree<&str, usize> = Default::default();
+
+    assert_eq!(tree.size(), 0);
+    assert_eq!(tree.first_key_value(), None);
+}
+
+#[test]
+fn test_snapshot() {
+    let mut tree = standard_tree();
+
+    let snapshot = tree.snapshot();
+
+    tree.put("S", 100);
+    tree.put("Z", 8);
+    tree.delete("A");
+    tree.delete_min();
+
+    assert_eq!(snapshot.size(), 8);
+    assert_eq!(snapshot.get(&"S"), Some(&0));
+    assert_eq!(snapshot.get(&"A"), Some(&3));
+    assert_eq!(snapshot.get(&"C"), Some(&5));
+    assert_eq!(snapshot.get(&"Z"), None);
+
+    // 快照的新版本不影响快照本身
+    let next = snapshot.put("B", 9);
+    assert_eq!(next.size(), 9);
+    assert_eq!(snapshot.get(&"B"), None);
+}
+
+#[test]
+fn test_neighbors_by_rank() {
+    let mut tree = RedBlackTree::new();
+
+    assert!(tree.neighbors_by_rank("H", 3).is_empty());
+
+    // A C E H M R S X
+    tree = standard_tree();
+
+    assert_eq!(tree.neighbors_by_rank("H", 3), [&"E", &"H", &"M"]);
+    // N 不存在，rank("N") 是它插入后的排名
+    assert_eq!(tree.neighbors_by_rank("N", 3), [&"M", &"R", &"S"]);
+    assert_eq!(tree.neighbors_by_rank("H", 4), [&"C", &"E", &"H", &"M"]);
+
+    // 靠近两端
+    assert_eq!(tree.neighbors_by_rank("A", 3), [&"A", &"C", &"E"]);
+    assert_eq!(tree.neighbors_by_rank("Z", 3), [&"R", &"S", &"X"]);
+
+    assert_eq!(tree.neighbors_by_rank("H", 20).len(), 8);
+    assert!(tree.neighbors_by_rank("H", 0).is_empty());
+}
+
+#[test]
+fn test_assert_invariants_random() {
+    use rand::{Rng, SeedableRng, XorShiftRng};
+
+    // 固定种子便于复现
+    let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+    let mut tree = RedBlackTree::new();
+    let mut keys: Vec<u16> = Vec::new();
+
+    for i in 0..3000 {
+        match rng.gen_range(0, 10) {
+            0..=5 => {
+                let key = rng.gen_range(0, 500);
+
+                if ! keys.contains(&key) {
+                    keys.push(key);
+                }
+
+                tree.put(key, i);
+            },
+            6..=7 if ! keys.is_empty() => {
+                let key = keys.swap_remove(rng.gen_range(0, keys.len()));
+                tree.delete(key);
+            },
+            8 if ! keys.is_empty() => {
+                let (key, _) = tree.pop_min().unwrap();
+                keys.retain(|&k| k != key);
+            },
+            9 if ! keys.is_empty() => {
+                let (key, _) = tree.pop_max().unwrap();
+                keys.retain(|&k| k != key);
+            },
+            _ => {},
+        }
+
+        tree.assert_invariants();
+        assert_eq!(tree.size(), keys.len());
+    }
+}
+
+#[test]
+#[should_panic(expected = "red-black tree invariant violated: node 'S' has size 5, expected 12")]
+fn test_assert_invariants_panic() {
+    let mut tree = RedBlackTree::new();
+
+    for (i, &key) in ['S', 'E', 'X', 'A', 'R'].iter().enumerate() {
+        tree.put(key, i);
+    }
+
+    tree.root.as_mut().unwrap().left.as_mut().unwrap().n = 10;
+    tree.assert_invariants();
+}
+
+#[test]
+fn test_levels() {
+    let mut tree = RedBlackTree::new();
+
+    assert!(tree.levels().is_empty());
+
+    tree = standard_tree();
+
+    let levels: Vec<Vec<&str>> = tree.levels().iter()
+        .map(|level| level.iter().map(|node| node.key).collect())
+        .collect();
+
+    //        M
+    //     E     S
+    //    C H   R X
+    //   A
+    assert_eq!(levels, [vec!["M"], vec!["E", "S"], vec!["C", "H", "R", "X"], vec!["A"]]);
+    assert_eq!(levels.len(), tree.height() + 1);
+
+    let flat: Vec<&str> = levels.iter().flat_map(|level| level.iter().cloned()).collect();
+    let expected: Vec<&str> = tree.level_order().iter().map(|node| node.key).collect();
+    assert_eq!(flat, expected);
+}
+
+#[test]
+fn test_to_pretty_string() {
+    let mut tree = RedBlackTree::new();
+
+    assert_eq!(tree.to_pretty_string(), "");
+
+    tree = standard_tree();
+
+    let pretty = tree.to_pretty_string();
+
+    assert_eq!(pretty, "\
+M [B]
+├── E [B]
+│   ├── C [B]
+│   │   └── A [R]
+│   └── H [B]
+└── S [B]
+    ├── R [B]
+    └── X [B]
+");
+    assert!(pretty.starts_with("M "));
+    assert_eq!(pretty.lines().count(), tree.size());
+}
+
+#[test]
+fn test_delete_range() {
+    let mut tree = standard_tree();
+
+    assert_eq!(tree.delete_range("C", "M"), 4);
+    assert_eq!(tree.in_order().iter().map(|node| node.key).collect::<Vec<_>>(), ["A", "R", "S", "X"]);
+    assert_eq!(tree.size(), 4);
+    tree.assert_invariants();
+
+    assert_eq!(tree.delete_range("B", "Q"), 0);
+    assert_eq!(tree.delete_range("0", "Z"), 4);
+    assert_eq!(tree.size(), 0);
+    assert_eq!(tree.validate(), Ok(()));
+}
+
+#[test]
+fn test_entry() {
+    let mut tree = RedBlackTree::new();
+    let text = "it was the best of times it was the worst of times it was";
+
+    for word in text.split_whitespace() {
+        tree.entry(word).and_modify(|count| *count += 1).or_insert(1);
+    }
+
+    assert_eq!(tree.get("it"), Some(&3));
+    assert_eq!(tree.get("was"), Some(&3));
+    assert_eq!(tree.get("times"), Some(&2));
+    assert_eq!(tree.get("best"), Some(&1));
+    assert_eq!(tree.size(), 7);
+    tree.assert_invariants();
+
+    // 返回的引用可以直接修改
+    *tree.entry("best").or_insert(0) += 10;
+    assert_eq!(tree.get("best"), Some(&11));
+
+    // 键已存在时不调用 f
+    let mut calls = 0;
+    tree.entry("worst").or_insert_with(|| { calls += 1; 0 });
+    assert_eq!(*tree.entry("zebra").or_insert_with(|| { calls += 1; 5 }), 5);
+    assert_eq!(calls, 1);
+    assert_eq!(tree.get("worst"), Some(&1));
+    assert_eq!(tree.size(), 8);
+    tree.assert_invariants();
+}
+
+#[test]
+fn test_get_many() {
+    let tree = standard_tree();
+
+    assert_eq!(tree.get_many(&["A", "B", "X", "A", "Z"]), [Some(&3), None, Some(&2), Some(&3), None]);
+    assert!(tree.get_many(&[]).is_empty());
+}
+
+#[test]
+fn test_insert() {
+    let mut tree = RedBlackTree::new();
+
+    assert_eq!(tree.insert("S", 1), None);
+    assert_eq!(tree.insert("E", 2), None);
+    assert_eq!(tree.insert("S", 3), Some(1));
+    assert_eq!(tree.insert("S", 4), Some(3));
+
+    assert_eq!(tree.get("S"), Some(&4));
+    assert_eq!(tree.size(), 2);
+    tree.assert_invariants();
+}
+
+#[test]
+fn test_entry_random() {
+    use rand::{Rng, SeedableRng, XorShiftRng};
+
+    let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+    let keys: Vec<usize> = (0..2000).map(|_| rng.gen_range(0, 500)).collect();
+    let mut tree = RedBlackTree::new();
+
+    // 插入会触发各种旋转，返回的引用必须指向刚插入的键
+    for (i, &key) in keys.iter().enumerate() {
+        *tree.entry(key).or_insert(0) += i;
+        tree.entry(key).and_modify(|val| *val += 1);
+    }
+
+    let mut expected = vec![None; 500];
+
+    for (i, &key) in keys.iter().enumerate() {
+        *expected[key].get_or_insert(0) += i + 1;
+    }
+
+    for key in 0..500 {
+        assert_eq!(tree.get(key), expected[key].as_ref());
+    }
+
+    tree.assert_invariants();
+}